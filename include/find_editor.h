@@ -0,0 +1,70 @@
+#ifndef FIND_EDITOR_H
+#define FIND_EDITOR_H
+
+/* Warning: this file is generated by cbindgen. Don't modify it manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// An editor's command, returned by [`find_editor_which`].
+//
+// Free it with [`find_editor_command_free`].
+typedef struct FindEditorCommand {
+  // The path to the editor's command.
+  char *path;
+  // The arguments that should be passed to the command.
+  char **args;
+  // The number of arguments.
+  size_t args_len;
+} FindEditorCommand;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Gets the name of an editor, which may include arguments, like `code --wait`.
+//
+// Returns `NULL` if the name contains a NUL byte. Free the name with
+// [`find_editor_string_free`].
+char *find_editor_name(void);
+
+// Gets the path to the editor's command and any arguments that should be passed to it.
+//
+// Returns `NULL` if the editor can't be found, or if it contains a NUL byte. Free the
+// command with [`find_editor_command_free`].
+FindEditorCommand *find_editor_which(void);
+
+// Opens an editor to edit `file`. Set `wait` to `true` to wait until the editor is
+// closed before returning.
+//
+// Returns `0` on success, or `-1` if `file` is `NULL` or the editor can't be opened.
+//
+// # Safety
+//
+// `file` must be `NULL` or a valid NUL-terminated string.
+int find_editor_open(const char *file, bool wait);
+
+// Frees a string returned by this library. Does nothing if `string` is `NULL`.
+//
+// # Safety
+//
+// `string` must be `NULL` or a string returned by this library that hasn't been freed.
+void find_editor_string_free(char *string);
+
+// Frees a command returned by [`find_editor_which`]. Does nothing if `command` is
+// `NULL`.
+//
+// # Safety
+//
+// `command` must be `NULL` or a command returned by [`find_editor_which`] that hasn't
+// been freed.
+void find_editor_command_free(FindEditorCommand *command);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FIND_EDITOR_H */
@@ -0,0 +1,134 @@
+//! Finds and opens an editor from the command line.
+//!
+//! Run `find-editor --help` for usage.
+use find_editor::{Error, Finder};
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Finds and opens your editor.
+
+Usage:
+    find-editor [--json]            Print the editor
+    find-editor open FILE [--wait]  Open FILE in the editor
+
+Options:
+    --json         Print the editor's command, arguments, path, and source as JSON
+    --wait         Wait until the editor is closed
+    -h, --help     Print this help
+    -V, --version  Print the version
+";
+
+/// What the user asked to do.
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    /// Print the editor, as JSON if `true`.
+    Print { json: bool },
+    /// Open a file in the editor.
+    Open { file: OsString, wait: bool },
+    /// Print the help.
+    Help,
+    /// Print the version.
+    Version,
+}
+
+fn main() -> ExitCode {
+    let command = match parse(std::env::args_os().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            let mut source = std::error::Error::source(&error);
+            while let Some(cause) = source {
+                eprintln!("  caused by: {cause}");
+                source = cause.source();
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses the command-line arguments, without the program's name.
+fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    let mut json = false;
+    let mut wait = false;
+    let mut open = false;
+    let mut file = None;
+    for arg in args {
+        match arg.to_str() {
+            Some("-h" | "--help") => return Ok(Command::Help),
+            Some("-V" | "--version") => return Ok(Command::Version),
+            Some("--json") => json = true,
+            Some("--wait") => wait = true,
+            Some("open") if !open => open = true,
+            Some(flag) if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+            _ if open && file.is_none() => file = Some(arg),
+            _ => return Err(format!("unexpected argument {}", arg.to_string_lossy())),
+        }
+    }
+    if !open {
+        if wait {
+            return Err(String::from("--wait can only be used with open"));
+        }
+        return Ok(Command::Print { json });
+    }
+    if json {
+        return Err(String::from("--json can't be used with open"));
+    }
+    let file = file.ok_or_else(|| String::from("open needs a FILE"))?;
+    Ok(Command::Open { file, wait })
+}
+
+/// Runs the command.
+fn run(command: Command) -> Result<(), Error> {
+    let finder = Finder::new();
+    let mut stdout = io::stdout().lock();
+    match command {
+        Command::Print { json: false } => {
+            let editor = finder.editor_name_os();
+            writeln!(stdout, "{}", editor.to_string_lossy()).map_err(Error::Io)
+        }
+        Command::Print { json: true } => {
+            let json = finder.resolve_json()?;
+            writeln!(stdout, "{json}").map_err(Error::Io)
+        }
+        Command::Open { file, wait } => finder.open_editor(file, wait),
+        Command::Help => write!(stdout, "{USAGE}").map_err(Error::Io),
+        Command::Version => {
+            let version = env!("CARGO_PKG_VERSION");
+            writeln!(stdout, "find-editor {version}").map_err(Error::Io)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::print(&[], Ok(Command::Print { json: false }))]
+    #[case::json(&["--json"], Ok(Command::Print { json: true }))]
+    #[case::open(&["open", "a.txt"], Ok(Command::Open { file: "a.txt".into(), wait: false }))]
+    #[case::open_wait(&["open", "--wait", "a.txt"], Ok(Command::Open { file: "a.txt".into(), wait: true }))]
+    #[case::help(&["open", "--help"], Ok(Command::Help))]
+    #[case::version(&["-V"], Ok(Command::Version))]
+    #[case::no_file(&["open"], Err("open needs a FILE"))]
+    #[case::unknown_option(&["--foo"], Err("unknown option --foo"))]
+    #[case::extra_argument(&["open", "a.txt", "b.txt"], Err("unexpected argument b.txt"))]
+    #[case::wait_without_open(&["--wait"], Err("--wait can only be used with open"))]
+    fn test_parse(#[case] args: &[&str], #[case] expected: Result<Command, &str>) {
+        let args = args.iter().map(OsString::from);
+        assert_eq!(expected.map_err(String::from), parse(args));
+    }
+}
@@ -0,0 +1,194 @@
+//! A C API for applications that aren't written in Rust.
+//!
+//! Build it as a C library with `cargo rustc --release --features capi --crate-type
+//! cdylib`. The functions are declared in `include/find_editor.h`, which is generated
+//! with `cbindgen --config cbindgen.toml --output include/find_editor.h`.
+//!
+//! These use the [global](crate::global) finder. Strings are UTF-8 on platforms other
+//! than Unix, where they are the platform's bytes. Strings returned by these functions
+//! must be freed with [`find_editor_string_free`].
+use crate::global;
+use std::ffi::{CStr, CString, OsString, c_char, c_int};
+use std::path::PathBuf;
+use std::ptr;
+
+/// An editor's command, returned by [`find_editor_which`].
+///
+/// Free it with [`find_editor_command_free`].
+#[repr(C)]
+pub struct FindEditorCommand {
+    /// The path to the editor's command.
+    pub path: *mut c_char,
+    /// The arguments that should be passed to the command.
+    pub args: *mut *mut c_char,
+    /// The number of arguments.
+    pub args_len: usize,
+}
+
+/// Gets the name of an editor, which may include arguments, like `code --wait`.
+///
+/// Returns `NULL` if the name contains a NUL byte. Free the name with
+/// [`find_editor_string_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn find_editor_name() -> *mut c_char {
+    into_c_string(global().editor_name_os()).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Gets the path to the editor's command and any arguments that should be passed to it.
+///
+/// Returns `NULL` if the editor can't be found, or if it contains a NUL byte. Free the
+/// command with [`find_editor_command_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn find_editor_which() -> *mut FindEditorCommand {
+    let Ok((path, args)) = global().which_editor_os() else {
+        return ptr::null_mut();
+    };
+    let Some(path) = into_c_string(PathBuf::into_os_string(path)) else {
+        return ptr::null_mut();
+    };
+    let Some(args) = args
+        .into_iter()
+        .map(into_c_string)
+        .collect::<Option<Vec<_>>>()
+    else {
+        return ptr::null_mut();
+    };
+    let args = args
+        .into_iter()
+        .map(CString::into_raw)
+        .collect::<Box<[_]>>();
+    let args_len = args.len();
+    let command = FindEditorCommand {
+        path: path.into_raw(),
+        args: Box::into_raw(args).cast(),
+        args_len,
+    };
+    Box::into_raw(Box::new(command))
+}
+
+/// Opens an editor to edit `file`. Set `wait` to `true` to wait until the editor is
+/// closed before returning.
+///
+/// Returns `0` on success, or `-1` if `file` is `NULL` or the editor can't be opened.
+///
+/// # Safety
+///
+/// `file` must be `NULL` or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn find_editor_open(file: *const c_char, wait: bool) -> c_int {
+    if file.is_null() {
+        return -1;
+    }
+    // SAFETY: The caller guarantees that file is a valid NUL-terminated string.
+    let file = unsafe { CStr::from_ptr(file) };
+    let Some(file) = from_c_str(file) else {
+        return -1;
+    };
+    match global().open_editor(file, wait) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+/// Frees a string returned by this library. Does nothing if `string` is `NULL`.
+///
+/// # Safety
+///
+/// `string` must be `NULL` or a string returned by this library that hasn't been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn find_editor_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: The caller guarantees that string was created by CString::into_raw.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Frees a command returned by [`find_editor_which`]. Does nothing if `command` is
+/// `NULL`.
+///
+/// # Safety
+///
+/// `command` must be `NULL` or a command returned by [`find_editor_which`] that hasn't
+/// been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn find_editor_command_free(command: *mut FindEditorCommand) {
+    if command.is_null() {
+        return;
+    }
+    // SAFETY: The caller guarantees that command was created by find_editor_which, so
+    //         it and its fields were created by Box::into_raw and CString::into_raw.
+    unsafe {
+        let command = Box::from_raw(command);
+        let args = Box::from_raw(ptr::slice_from_raw_parts_mut(
+            command.args,
+            command.args_len,
+        ));
+        for arg in args {
+            find_editor_string_free(arg);
+        }
+        find_editor_string_free(command.path);
+    }
+}
+
+/// Converts `value` into a C string, or `None` if it contains a NUL byte.
+fn into_c_string(value: OsString) -> Option<CString> {
+    #[cfg(unix)]
+    let bytes = std::os::unix::ffi::OsStringExt::into_vec(value);
+    #[cfg(not(unix))]
+    let bytes = value.to_string_lossy().into_owned().into_bytes();
+    CString::new(bytes).ok()
+}
+
+/// Converts a C string into an [`OsString`], or `None` if it isn't valid UTF-8 on
+/// platforms other than Unix.
+fn from_c_str(value: &CStr) -> Option<OsString> {
+    #[cfg(unix)]
+    let value = {
+        use std::os::unix::ffi::OsStrExt;
+        std::ffi::OsStr::from_bytes(value.to_bytes()).to_owned()
+    };
+    #[cfg(not(unix))]
+    let value = OsString::from(value.to_str().ok()?);
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name() {
+        let name = find_editor_name();
+        assert!(!name.is_null());
+        // SAFETY: name was returned by find_editor_name.
+        unsafe {
+            assert_eq!(
+                global().editor_name().as_bytes(),
+                CStr::from_ptr(name).to_bytes()
+            );
+            find_editor_string_free(name);
+        }
+    }
+
+    #[test]
+    fn test_command_free() {
+        let args = ["-c", "--wait"].map(|arg| CString::new(arg).unwrap().into_raw());
+        let command = FindEditorCommand {
+            path: CString::new("/usr/bin/vim").unwrap().into_raw(),
+            args: Box::into_raw(Box::new(args)).cast(),
+            args_len: 2,
+        };
+        // SAFETY: The command was created like find_editor_which creates one.
+        unsafe { find_editor_command_free(Box::into_raw(Box::new(command))) };
+    }
+
+    #[test]
+    fn test_null() {
+        // SAFETY: NULL is allowed.
+        unsafe {
+            assert_eq!(-1, find_editor_open(ptr::null(), false));
+            find_editor_string_free(ptr::null_mut());
+            find_editor_command_free(ptr::null_mut());
+        }
+    }
+}
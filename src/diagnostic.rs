@@ -0,0 +1,136 @@
+//! Implements [`miette::Diagnostic`] for this crate's [`Error`].
+use crate::{Error, ErrorKind};
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+use std::fmt::Display;
+
+impl ErrorKind {
+    /// Gets the diagnostic code for this kind of error.
+    fn code(self) -> &'static str {
+        match self {
+            Self::NoEditor => "find_editor::no_editor",
+            Self::Parse => "find_editor::parse",
+            Self::NotFound => "find_editor::not_found",
+            Self::NotAllowed => "find_editor::not_allowed",
+            Self::Spawn => "find_editor::spawn",
+            Self::EditorExited => "find_editor::editor_exited",
+            Self::TimedOut => "find_editor::timed_out",
+            Self::Cancelled => "find_editor::cancelled",
+            Self::Unsupported => "find_editor::unsupported",
+            Self::Io => "find_editor::io",
+        }
+    }
+}
+
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(self.kind().code()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = match self {
+            Self::NoEditorConfigured => {
+                String::from("set $VISUAL or $EDITOR to your editor, like `export EDITOR=vim`")
+            }
+            Self::NonUnicodeValue { var } => format!("set {var} to valid unicode"),
+            #[cfg(feature = "split")]
+            Self::ShellWords(_) | Self::InvalidEditor { .. } => {
+                String::from("check that every quote in the editor is closed")
+            }
+            #[cfg(feature = "which")]
+            Self::EditorNotFound {
+                suggestion: Some(suggestion),
+                ..
+            } => format!("did you mean '{suggestion}'? Set $EDITOR or $VISUAL to a valid command"),
+            #[cfg(feature = "which")]
+            Self::EditorNotFound { command, .. } => {
+                format!("install {command}, or set $EDITOR or $VISUAL to a valid command")
+            }
+            #[cfg(feature = "which")]
+            Self::RelativeEditorPath(_) => String::from("use the editor's absolute path"),
+            #[cfg(feature = "open")]
+            Self::Spawn { source, .. } if source.kind() == std::io::ErrorKind::NotFound => {
+                String::from("set $EDITOR or $VISUAL to a command that's installed")
+            }
+            #[cfg(feature = "open")]
+            Self::DirectoryUnsupported { .. } => {
+                String::from("choose an editor that can open directories, like VS Code")
+            }
+            // NOTE Without these features, every error has help.
+            #[cfg_attr(
+                not(any(feature = "which", feature = "open")),
+                allow(unreachable_patterns)
+            )]
+            _ => return None,
+        };
+        Some(Box::new(help))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        match self {
+            #[cfg(feature = "split")]
+            Self::InvalidEditor { editor, .. } => Some(editor),
+            #[cfg(feature = "which")]
+            Self::EditorNotFound { command, .. } => Some(command),
+            #[cfg(feature = "open")]
+            Self::Spawn { command, .. } => Some(command),
+            _ => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let labeled: Option<(&String, String)> = match self {
+            #[cfg(feature = "split")]
+            Self::InvalidEditor { editor, source } => Some((editor, source.to_string())),
+            #[cfg(feature = "which")]
+            Self::EditorNotFound { command, .. } => {
+                Some((command, String::from("not found on $PATH")))
+            }
+            #[cfg(feature = "open")]
+            Self::Spawn { command, source } => Some((command, source.to_string())),
+            _ => None,
+        };
+        let (source, label) = labeled?;
+        let span = LabeledSpan::at(0..source.len(), label);
+        Some(Box::new(std::iter::once(span)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "which")]
+    #[test]
+    fn test_help_suggestion() {
+        let e = Error::EditorNotFound {
+            command: String::from("virm"),
+            suggestion: Some(String::from("vim")),
+            source: which::Error::CannotFindBinaryPath,
+        };
+        assert_eq!(
+            Some("find_editor::not_found"),
+            e.code().map(|code| code.to_string()).as_deref()
+        );
+        assert_eq!(
+            Some("did you mean 'vim'? Set $EDITOR or $VISUAL to a valid command"),
+            e.help().map(|help| help.to_string()).as_deref()
+        );
+        let labels = e.labels().unwrap().collect::<Vec<_>>();
+        assert_eq!(
+            [LabeledSpan::at(0..4, "not found on $PATH")],
+            labels.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_help_without_labels() {
+        let e = Error::NonUnicodeValue {
+            var: String::from("$EDITOR"),
+        };
+        assert_eq!(
+            Some("set $EDITOR to valid unicode"),
+            e.help().map(|help| help.to_string()).as_deref()
+        );
+        assert!(e.labels().is_none());
+    }
+}
@@ -0,0 +1,331 @@
+//! Module for metadata about well-known editors.
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+/// Metadata about a well-known editor.
+///
+/// # Example
+///
+/// ```rust
+/// use find_editor::EditorInfo;
+///
+/// let info = EditorInfo::for_command("code").expect("VS Code should be known");
+/// assert!(info.gui);
+/// assert_eq!(Some("--wait"), info.wait_flag);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditorInfo {
+    /// The editor's display name.
+    pub name: &'static str,
+    /// The commands that run the editor, without any file extension.
+    pub commands: &'static [&'static str],
+    /// If the editor opens a graphical window instead of running in the terminal.
+    pub gui: bool,
+    /// The flag that makes the editor block until it is closed, if it doesn't block by
+    /// default.
+    pub wait_flag: Option<&'static str>,
+    /// How to open a file at a line and column, if the editor supports it.
+    pub goto: Option<GotoSyntax>,
+    /// The flag that opens the editor in read-only mode, if it has one.
+    pub read_only_flag: Option<&'static str>,
+    /// The flag that opens files in an existing window of the editor, if it has one.
+    pub reuse_window_flag: Option<&'static str>,
+    /// The argument that makes the editor read text from stdin, if it can.
+    pub stdin_arg: Option<&'static str>,
+    /// If the editor treats every argument after `--` as a file.
+    pub end_of_options: bool,
+    /// If the editor can open a directory.
+    pub opens_directories: bool,
+    /// If the editor can be opened without a file, and still be waited for.
+    pub opens_without_file: bool,
+}
+
+/// How an editor opens a file at a line and column.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GotoSyntax {
+    /// `+LINE file`, or `"+call cursor(LINE, COLUMN)" file` (e.g. vim).
+    Vim,
+    /// `+LINE,COLUMN file` (e.g. nano).
+    PlusComma,
+    /// `+LINE:COLUMN file` (e.g. emacs).
+    PlusColon,
+    /// `--goto file:LINE:COLUMN` (e.g. VS Code).
+    Goto,
+    /// `file:LINE:COLUMN` (e.g. Sublime Text).
+    Suffix,
+    /// `--line LINE --column COLUMN file` (e.g. JetBrains IDEs).
+    Line,
+}
+
+impl EditorInfo {
+    /// Every known editor.
+    const ALL: &'static [Self] = &[
+        Self::terminal("Vim", &["vi", "vim", "nvim", "view"])
+            .goto(GotoSyntax::Vim)
+            .read_only_flag("-R")
+            .stdin_arg("-")
+            .end_of_options()
+            .opens_directories(),
+        Self::gui("gVim", &["gvim", "mvim"])
+            .wait_flag("-f")
+            .goto(GotoSyntax::Vim)
+            .read_only_flag("-R")
+            .reuse_window_flag("--remote")
+            .end_of_options()
+            .opens_directories(),
+        Self::terminal("GNU nano", &["nano"])
+            .goto(GotoSyntax::PlusComma)
+            .read_only_flag("-v")
+            .stdin_arg("-")
+            .end_of_options(),
+        Self::terminal("Emacs", &["emacs", "emacsclient"])
+            .goto(GotoSyntax::PlusColon)
+            .end_of_options()
+            .opens_directories(),
+        Self::terminal("Kakoune", &["kak"])
+            .goto(GotoSyntax::PlusColon)
+            .read_only_flag("-ro"),
+        Self::terminal("Helix", &["hx", "helix"])
+            .goto(GotoSyntax::Suffix)
+            .opens_directories(),
+        Self::terminal("micro", &["micro"]).goto(GotoSyntax::PlusColon),
+        Self::gui("Visual Studio Code", &["code", "code-insiders", "codium"])
+            .wait_flag("--wait")
+            .goto(GotoSyntax::Goto)
+            .reuse_window_flag("--reuse-window")
+            .stdin_arg("-")
+            .opens_directories(),
+        // NOTE Sublime Text opens files in its last window by default.
+        Self::gui("Sublime Text", &["subl"])
+            .wait_flag("-w")
+            .goto(GotoSyntax::Suffix)
+            .stdin_arg("-")
+            .opens_directories(),
+        Self::gui("Zed", &["zed"])
+            .wait_flag("--wait")
+            .goto(GotoSyntax::Suffix)
+            .reuse_window_flag("--add")
+            .opens_directories(),
+        Self::gui("Atom", &["atom"])
+            .wait_flag("--wait")
+            .goto(GotoSyntax::Suffix)
+            .opens_directories(),
+        Self::gui("TextMate", &["mate"])
+            .wait_flag("-w")
+            .opens_directories(),
+        Self::gui("gedit", &["gedit"])
+            .wait_flag("--wait")
+            .goto(GotoSyntax::PlusColon)
+            .stdin_arg("-"),
+        Self::jetbrains("IntelliJ IDEA", &["idea", "idea64"]),
+        Self::jetbrains("PyCharm", &["pycharm", "pycharm64", "charm"]),
+        Self::jetbrains("CLion", &["clion", "clion64"]),
+        Self::jetbrains("GoLand", &["goland", "goland64"]),
+        Self::jetbrains("WebStorm", &["webstorm", "webstorm64"]),
+        Self::jetbrains("PhpStorm", &["phpstorm", "phpstorm64"]),
+        Self::jetbrains("RubyMine", &["rubymine", "rubymine64"]),
+        Self::jetbrains("Rider", &["rider", "rider64"]),
+        Self::jetbrains("RustRover", &["rustrover", "rustrover64"]),
+        Self::gui("Notepad++", &["notepad++"]),
+        Self::gui("Notepad", &["notepad"]),
+    ];
+
+    /// Finds the metadata for an editor's command.
+    ///
+    /// `command` can be a command name or a path, with or without an extension like
+    /// `.exe`. Returns `None` if the editor isn't known.
+    pub fn for_command<S>(command: S) -> Option<&'static Self>
+    where
+        S: AsRef<OsStr>,
+    {
+        let name = Path::new(command.as_ref()).file_stem()?.to_str()?;
+        Self::ALL.iter().find(|info| {
+            info.commands
+                .iter()
+                .any(|command| command.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Every known editor.
+    #[inline]
+    pub fn all() -> &'static [Self] {
+        Self::ALL
+    }
+
+    /// Creates metadata for a terminal editor.
+    const fn terminal(name: &'static str, commands: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            commands,
+            gui: false,
+            wait_flag: None,
+            goto: None,
+            read_only_flag: None,
+            reuse_window_flag: None,
+            stdin_arg: None,
+            end_of_options: false,
+            opens_directories: false,
+            opens_without_file: true,
+        }
+    }
+
+    /// Creates metadata for a graphical editor.
+    const fn gui(name: &'static str, commands: &'static [&'static str]) -> Self {
+        Self {
+            gui: true,
+            ..Self::terminal(name, commands)
+        }
+    }
+
+    /// Creates metadata for a JetBrains IDE, whose launchers share their flags.
+    const fn jetbrains(name: &'static str, commands: &'static [&'static str]) -> Self {
+        Self::gui(name, commands)
+            .wait_flag("--wait")
+            .goto(GotoSyntax::Line)
+            .opens_directories()
+    }
+
+    /// Sets the wait flag. Editors with a wait flag wait until a file is closed, so
+    /// they need a file.
+    const fn wait_flag(self, wait_flag: &'static str) -> Self {
+        Self {
+            wait_flag: Some(wait_flag),
+            opens_without_file: false,
+            ..self
+        }
+    }
+
+    /// Sets the goto syntax.
+    const fn goto(self, goto: GotoSyntax) -> Self {
+        Self {
+            goto: Some(goto),
+            ..self
+        }
+    }
+
+    /// Sets the read-only flag.
+    const fn read_only_flag(self, read_only_flag: &'static str) -> Self {
+        Self {
+            read_only_flag: Some(read_only_flag),
+            ..self
+        }
+    }
+
+    /// Sets the reuse-window flag.
+    const fn reuse_window_flag(self, reuse_window_flag: &'static str) -> Self {
+        Self {
+            reuse_window_flag: Some(reuse_window_flag),
+            ..self
+        }
+    }
+
+    /// Sets the argument that reads from stdin.
+    const fn stdin_arg(self, stdin_arg: &'static str) -> Self {
+        Self {
+            stdin_arg: Some(stdin_arg),
+            ..self
+        }
+    }
+
+    /// Marks that the editor treats every argument after `--` as a file.
+    const fn end_of_options(self) -> Self {
+        Self {
+            end_of_options: true,
+            ..self
+        }
+    }
+
+    /// Marks that the editor can open directories.
+    const fn opens_directories(self) -> Self {
+        Self {
+            opens_directories: true,
+            ..self
+        }
+    }
+}
+
+impl GotoSyntax {
+    /// Gets the arguments that open `file` at a `line` and, optionally, a `column`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::GotoSyntax;
+    ///
+    /// let args = GotoSyntax::Goto.args("main.rs", 10, Some(5));
+    /// assert_eq!(["--goto", "main.rs:10:5"], args.as_slice());
+    /// ```
+    pub fn args<P>(self, file: P, line: usize, column: Option<usize>) -> Vec<OsString>
+    where
+        P: AsRef<Path>,
+    {
+        let file = file.as_ref();
+        let position = |separator: &str| match column {
+            Some(column) => format!("{line}{separator}{column}"),
+            None => line.to_string(),
+        };
+        let with_position = || {
+            let mut arg = OsString::from(file);
+            arg.push(format!(":{}", position(":")));
+            arg
+        };
+        match self {
+            Self::Vim => {
+                let command = match column {
+                    Some(column) => format!("+call cursor({line}, {column})"),
+                    None => format!("+{line}"),
+                };
+                vec![command.into(), file.into()]
+            }
+            Self::PlusComma => vec![format!("+{}", position(",")).into(), file.into()],
+            Self::PlusColon => vec![format!("+{}", position(":")).into(), file.into()],
+            Self::Goto => vec!["--goto".into(), with_position()],
+            Self::Suffix => vec![with_position()],
+            Self::Line => {
+                let mut args = vec!["--line".into(), line.to_string().into()];
+                if let Some(column) = column {
+                    args.extend(["--column".into(), column.to_string().into()]);
+                }
+                args.push(file.into());
+                args
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::name("vim", Some("Vim"))]
+    #[case::path("/usr/bin/nvim", Some("Vim"))]
+    #[case::extension("Code.exe", Some("Visual Studio Code"))]
+    #[case::jetbrains("idea64.exe", Some("IntelliJ IDEA"))]
+    #[case::unknown("--UNKNOWN--", None)]
+    fn test_for_command(#[case] command: &str, #[case] expected: Option<&str>) {
+        let actual = EditorInfo::for_command(command).map(|info| info.name);
+        assert_eq!(expected, actual);
+    }
+
+    #[rstest]
+    #[case::vim_line(GotoSyntax::Vim, None, &["+10", "f.txt"])]
+    #[case::vim_column(GotoSyntax::Vim, Some(5), &["+call cursor(10, 5)", "f.txt"])]
+    #[case::plus_comma(GotoSyntax::PlusComma, Some(5), &["+10,5", "f.txt"])]
+    #[case::plus_colon(GotoSyntax::PlusColon, Some(5), &["+10:5", "f.txt"])]
+    #[case::goto(GotoSyntax::Goto, Some(5), &["--goto", "f.txt:10:5"])]
+    #[case::suffix(GotoSyntax::Suffix, None, &["f.txt:10"])]
+    #[case::line(GotoSyntax::Line, None, &["--line", "10", "f.txt"])]
+    #[case::line_column(GotoSyntax::Line, Some(5), &["--line", "10", "--column", "5", "f.txt"])]
+    fn test_goto_args(
+        #[case] goto: GotoSyntax,
+        #[case] column: Option<usize>,
+        #[case] expected: &[&str],
+    ) {
+        let actual = goto.args("f.txt", 10, column);
+        assert_eq!(expected, actual.as_slice());
+    }
+}
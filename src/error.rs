@@ -2,34 +2,384 @@
 use std::error::Error as ErrorTrait;
 use std::fmt::{self, Display};
 
-/// Possible error type returned by 3rd-party tools.
+/// Possible errors when finding or opening an editor.
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum Error {
-    /// A command failed to start.
+    /// No editor was configured, and falling back to a default editor was not allowed.
+    NoEditorConfigured,
+    /// The configured editor isn't valid unicode. See
+    /// [`Finder::try_editor_name`](crate::Finder::try_editor_name).
+    NonUnicodeValue {
+        /// Where the editor was found, like `$EDITOR`.
+        var: String,
+    },
+    /// An I/O operation failed, like reading or writing a file.
     #[cfg(feature = "open")]
     Io(std::io::Error),
+    /// The editor's command failed to start.
+    #[cfg(feature = "open")]
+    Spawn {
+        /// The command line that failed to start.
+        command: String,
+        /// The reason the command failed to start.
+        source: std::io::Error,
+    },
+    /// The editor didn't exit before the timeout, and was terminated.
+    #[cfg(feature = "open")]
+    TimedOut,
+    /// The editor exited with a non-zero exit status, like with `:cq` in vim. See
+    /// [`OpenOptions::fail_on_nonzero`](crate::OpenOptions::fail_on_nonzero).
+    #[cfg(feature = "open")]
+    EditorExited {
+        /// The editor's exit status.
+        status: std::process::ExitStatus,
+        /// The end of what the editor wrote to stderr, if it was captured with
+        /// [`OpenOptions::capture_stderr`](crate::OpenOptions::capture_stderr).
+        stderr: Option<String>,
+    },
+    /// Waiting for the editor was cancelled, and the editor was terminated.
+    #[cfg(feature = "open")]
+    Cancelled,
+    /// Editors can't be opened on this platform, like on WebAssembly, which can't spawn
+    /// processes.
+    #[cfg(feature = "open")]
+    UnsupportedPlatform,
+    /// The editor can't open directories, like Notepad. See
+    /// [`OpenOptions::open_path`](crate::OpenOptions::open_path).
+    #[cfg(feature = "open")]
+    DirectoryUnsupported {
+        /// The editor's name.
+        editor: &'static str,
+    },
     /// An error returned when failing to split shell words using
-    /// [`shell-words`](https://crates.io/crates/shell-words).
+    /// [`shell-words`](https://crates.io/crates/shell-words). Splitting the editor fails
+    /// with [`Error::InvalidEditor`] instead, which includes the editor.
     #[cfg(feature = "split")]
     ShellWords(super::ParseError),
+    /// The editor couldn't be split into its command and arguments, like when it has a
+    /// quote that isn't closed.
+    #[cfg(feature = "split")]
+    InvalidEditor {
+        /// The editor that couldn't be split.
+        editor: String,
+        /// The reason the editor couldn't be split.
+        source: super::ParseError,
+    },
     /// An error returned when failing to find a command with
     /// [`which`](https://crates.io/crates/which).
     #[cfg(feature = "which")]
     Which(super::WhichError),
+    /// The editor's command couldn't be found on `$PATH`.
+    #[cfg(feature = "which")]
+    EditorNotFound {
+        /// The command that couldn't be found.
+        command: String,
+        /// A command on `$PATH` with a similar name, if any. See
+        /// [`Error::suggestion`].
+        suggestion: Option<String>,
+        /// The reason the command couldn't be found.
+        source: super::WhichError,
+    },
+    /// The editor's command is a relative path, which isn't allowed. See
+    /// [`FinderBuilder::relative_editor_paths`](crate::FinderBuilder::relative_editor_paths).
+    #[cfg(feature = "which")]
+    RelativeEditorPath(std::path::PathBuf),
+}
+
+/// The kind of an [`Error`], which is available with every combination of features.
+///
+/// See [`Error::kind`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// No editor was configured.
+    NoEditor,
+    /// The editor couldn't be parsed, like when it isn't valid unicode or has a quote
+    /// that isn't closed.
+    Parse,
+    /// The editor's command couldn't be found.
+    NotFound,
+    /// The editor isn't allowed, like a relative path.
+    NotAllowed,
+    /// The editor's command failed to start.
+    Spawn,
+    /// The editor exited with a non-zero exit status.
+    EditorExited,
+    /// The editor didn't exit before the timeout.
+    TimedOut,
+    /// Waiting for the editor was cancelled.
+    Cancelled,
+    /// The editor can't do what was asked, or editors can't be opened on this platform.
+    Unsupported,
+    /// An I/O operation failed.
+    Io,
+}
+
+impl Error {
+    /// Gets the kind of error, to handle classes of errors without matching every
+    /// variant.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::{ErrorKind, Finder};
+    ///
+    /// match Finder::new().try_editor_name() {
+    ///     Ok(editor) => println!("The editor is {editor}"),
+    ///     Err(e) if e.kind() == ErrorKind::Parse => eprintln!("Please fix $EDITOR: {e}"),
+    ///     Err(e) => eprintln!("Couldn't find an editor: {e}"),
+    /// }
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NoEditorConfigured => ErrorKind::NoEditor,
+            Self::NonUnicodeValue { .. } => ErrorKind::Parse,
+            #[cfg(feature = "open")]
+            Self::Io(_) => ErrorKind::Io,
+            #[cfg(feature = "open")]
+            Self::Spawn { .. } => ErrorKind::Spawn,
+            #[cfg(feature = "open")]
+            Self::TimedOut => ErrorKind::TimedOut,
+            #[cfg(feature = "open")]
+            Self::EditorExited { .. } => ErrorKind::EditorExited,
+            #[cfg(feature = "open")]
+            Self::Cancelled => ErrorKind::Cancelled,
+            #[cfg(feature = "open")]
+            Self::UnsupportedPlatform | Self::DirectoryUnsupported { .. } => ErrorKind::Unsupported,
+            #[cfg(feature = "split")]
+            Self::ShellWords(_) | Self::InvalidEditor { .. } => ErrorKind::Parse,
+            #[cfg(feature = "which")]
+            Self::Which(_) | Self::EditorNotFound { .. } => ErrorKind::NotFound,
+            #[cfg(feature = "which")]
+            Self::RelativeEditorPath(_) => ErrorKind::NotAllowed,
+        }
+    }
+
+    /// Checks if the editor's command couldn't be found.
+    ///
+    /// This includes an editor that failed to start because its command doesn't exist,
+    /// which happens without the `which` feature.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            #[cfg(feature = "open")]
+            Self::Spawn { source, .. } => source.kind() == std::io::ErrorKind::NotFound,
+            _ => self.kind() == ErrorKind::NotFound,
+        }
+    }
+
+    /// Checks if the editor couldn't be parsed.
+    #[inline]
+    pub fn is_parse_error(&self) -> bool {
+        self.kind() == ErrorKind::Parse
+    }
+
+    /// Checks if the editor's command failed to start.
+    #[inline]
+    pub fn is_spawn_failure(&self) -> bool {
+        self.kind() == ErrorKind::Spawn
+    }
+
+    /// A command that the user may have meant, when the editor's command couldn't be
+    /// found. For example, `vim` when `$EDITOR` is `virm`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// if let Err(e) = Finder::new().which_editor() {
+    ///     if let Some(suggestion) = e.suggestion() {
+    ///         eprintln!("Try setting $EDITOR to {suggestion}");
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "which")]
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            Self::EditorNotFound { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::NoEditorConfigured => write!(f, "no editor is configured"),
+            Self::NonUnicodeValue { var } => write!(f, "{var} is not valid unicode"),
             #[cfg(feature = "open")]
             Self::Io(e) => Display::fmt(e, f),
+            #[cfg(feature = "open")]
+            Self::Spawn { command, .. } => write!(f, "failed to run {command}"),
+            #[cfg(feature = "open")]
+            Self::TimedOut => write!(f, "timed out waiting for the editor"),
+            #[cfg(feature = "open")]
+            Self::EditorExited { status, stderr } => match stderr.as_deref() {
+                Some(stderr) if !stderr.is_empty() => {
+                    write!(f, "the editor exited unsuccessfully ({status}): {stderr}")
+                }
+                _ => write!(f, "the editor exited unsuccessfully ({status})"),
+            },
+            #[cfg(feature = "open")]
+            Self::Cancelled => write!(f, "cancelled waiting for the editor"),
+            #[cfg(feature = "open")]
+            Self::UnsupportedPlatform => write!(f, "editors can't be opened on this platform"),
+            #[cfg(feature = "open")]
+            Self::DirectoryUnsupported { editor } => write!(f, "{editor} can't open directories"),
             #[cfg(feature = "split")]
             Self::ShellWords(e) => Display::fmt(e, f),
+            #[cfg(feature = "split")]
+            Self::InvalidEditor { editor, .. } => {
+                write!(f, "the editor '{editor}' couldn't be split into words")
+            }
             #[cfg(feature = "which")]
             Self::Which(e) => Display::fmt(e, f),
+            #[cfg(feature = "which")]
+            Self::EditorNotFound {
+                command,
+                suggestion: Some(suggestion),
+                ..
+            } => write!(f, "{command} not found; did you mean '{suggestion}'?"),
+            #[cfg(feature = "which")]
+            Self::EditorNotFound { command, .. } => write!(f, "{command} not found"),
+            #[cfg(feature = "which")]
+            Self::RelativeEditorPath(path) => write!(
+                f,
+                "the editor {} is a relative path, which isn't allowed",
+                path.display()
+            ),
         }
     }
 }
 
-impl ErrorTrait for Error {}
+impl ErrorTrait for Error {
+    fn source(&self) -> Option<&(dyn ErrorTrait + 'static)> {
+        match self {
+            // NOTE These errors are displayed as the underlying error, so they share
+            //      its source instead of repeating it.
+            #[cfg(feature = "open")]
+            Self::Io(e) => e.source(),
+            #[cfg(feature = "split")]
+            Self::ShellWords(e) => e.source(),
+            #[cfg(feature = "which")]
+            Self::Which(e) => e.source(),
+            #[cfg(feature = "open")]
+            Self::Spawn { source, .. } => Some(source),
+            #[cfg(feature = "split")]
+            Self::InvalidEditor { source, .. } => Some(source),
+            #[cfg(feature = "which")]
+            Self::EditorNotFound { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "open")]
+impl From<std::io::Error> for Error {
+    #[inline]
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "split")]
+impl From<super::ParseError> for Error {
+    #[inline]
+    fn from(e: super::ParseError) -> Self {
+        Self::ShellWords(e)
+    }
+}
+
+#[cfg(feature = "which")]
+impl From<super::WhichError> for Error {
+    #[inline]
+    fn from(e: super::WhichError) -> Self {
+        Self::Which(e)
+    }
+}
+
+/// Possible errors when editing a value with [`Finder::edit_serde`].
+///
+/// [`Finder::edit_serde`]: crate::Finder::edit_serde
+#[cfg(feature = "serde")]
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum EditError {
+    /// The editor failed to open.
+    Editor(Error),
+    /// The value failed to serialize.
+    Serialize(crate::format::BoxError),
+    /// The edited text failed to parse. The edited text is kept so that it can be
+    /// edited again.
+    Parse {
+        /// The edited text.
+        text: String,
+        /// The reason the text failed to parse.
+        source: crate::format::BoxError,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Editor(e) => Display::fmt(e, f),
+            Self::Serialize(e) => write!(f, "failed to serialize the value: {e}"),
+            Self::Parse { source, .. } => write!(f, "failed to parse the edited text: {source}"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ErrorTrait for EditError {
+    fn source(&self) -> Option<&(dyn ErrorTrait + 'static)> {
+        match self {
+            Self::Editor(e) => Some(e),
+            Self::Serialize(e) | Self::Parse { source: e, .. } => Some(e.as_ref()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Error> for EditError {
+    fn from(e: Error) -> Self {
+        Self::Editor(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(ErrorKind::NoEditor, Error::NoEditorConfigured.kind());
+        let e = Error::NonUnicodeValue {
+            var: String::from("$EDITOR"),
+        };
+        assert!(e.is_parse_error());
+        assert!(!e.is_not_found());
+    }
+
+    #[cfg(feature = "open")]
+    #[test]
+    fn test_from_io_error() {
+        fn read(path: &str) -> crate::Result<String> {
+            Ok(std::fs::read_to_string(path)?)
+        }
+        let e = read("/nonexistent/notes.txt").unwrap_err();
+        assert_eq!(ErrorKind::Io, e.kind());
+    }
+
+    #[cfg(feature = "open")]
+    #[test]
+    fn test_is_not_found_spawn() {
+        let e = Error::Spawn {
+            command: String::from("virm notes.txt"),
+            source: std::io::ErrorKind::NotFound.into(),
+        };
+        assert!(e.is_spawn_failure());
+        assert!(e.is_not_found());
+    }
+}
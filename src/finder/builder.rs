@@ -0,0 +1,396 @@
+//! Contains the [`FinderBuilder`] struct which helps configure a [`Finder`].
+#[cfg(feature = "split")]
+use super::SplitStyle;
+use super::{EnvSource, Finder, Resolver, Source};
+use std::ffi::{OsStr, OsString};
+#[cfg(feature = "which")]
+use std::path::Path;
+use std::sync::Arc;
+
+/// Builder for a [`Finder`].
+///
+/// Create one with [`Finder::builder`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FinderBuilder {
+    /// The finder being configured.
+    finder: Finder,
+}
+
+impl FinderBuilder {
+    /// Creates a new [`FinderBuilder`] with the default configuration.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets where environment variables are read from. This defaults to
+    /// [`ProcessEnv`](super::ProcessEnv), which reads the process's environment.
+    ///
+    /// See [`EnvSource`] for more information.
+    pub fn env_source<E>(mut self, env: E) -> Self
+    where
+        E: EnvSource + 'static,
+    {
+        self.finder.env = Some(Arc::new(env));
+        self
+    }
+
+    /// Adds extra environment variables to look up.
+    ///
+    /// The extra environment variables always take priority over the standard ones,
+    /// and are looked up in the order they are added.
+    pub fn extra_environment_variables<S, I>(mut self, extras: I) -> Self
+    where
+        S: AsRef<OsStr>,
+        I: IntoIterator<Item = S>,
+    {
+        self.finder
+            .extra_env_vars
+            .extend(extras.into_iter().map(|s| OsString::from(s.as_ref())));
+        self
+    }
+
+    /// Sets the editor from your application's config, if it has one.
+    ///
+    /// This is searched after the extra environment variables and before the custom
+    /// resolvers. Use [`FinderBuilder::sources`] with [`Source::Config`] to choose a
+    /// different priority. This is the same as adding [`Source::Config`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::{Finder, Source};
+    ///
+    /// let configured_editor = Some("nano");
+    /// // Prefer $EDITOR over the config.
+    /// let finder = Finder::builder()
+    ///     .config_value(configured_editor)
+    ///     .sources([Source::StandardEnv, Source::Config])
+    ///     .build();
+    /// ```
+    pub fn config_value<S>(mut self, value: Option<S>) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.finder.config_value = value.map(|value| OsString::from(value.as_ref()));
+        self.toggle_source(Source::Config, true)
+    }
+
+    /// Adds a custom [`Resolver`] to find an editor with.
+    ///
+    /// Custom resolvers are used after the extra environment variables and before the
+    /// standard ones, in the order they are added.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    /// use std::ffi::OsString;
+    ///
+    /// let configured_editor = Some("nano");
+    /// let finder = Finder::builder()
+    ///     .resolver(move || configured_editor.map(OsString::from))
+    ///     .build();
+    /// ```
+    pub fn resolver<R>(mut self, resolver: R) -> Self
+    where
+        R: Resolver + 'static,
+    {
+        self.finder.resolvers.push(Arc::new(resolver));
+        self
+    }
+
+    /// Sets the standard environment variables to look up, in order.
+    ///
+    /// This replaces the defaults, `$VISUAL` and `$EDITOR`.
+    pub fn standard_environment_variables<S, I>(mut self, keys: I) -> Self
+    where
+        S: AsRef<OsStr>,
+        I: IntoIterator<Item = S>,
+    {
+        self.finder.standard_env_vars = keys
+            .into_iter()
+            .map(|s| OsString::from(s.as_ref()))
+            .collect();
+        self
+    }
+
+    /// Sets the editor to use when no environment variable is defined.
+    ///
+    /// The default is [`Finder::DEFAULT_FALLBACKS`].
+    #[inline]
+    pub fn fallback<S>(self, fallback: S) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.fallbacks([fallback])
+    }
+
+    /// Sets a prioritized list of editors to use when no environment variable is
+    /// defined.
+    ///
+    /// With the `which` feature, the first fallback that can be found on `$PATH` is
+    /// used. If none of them can be found, the last fallback is used, so put the
+    /// editor that's most likely to be installed last. Without the `which` feature,
+    /// the first fallback is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::builder()
+    ///     .fallbacks(["hx", "nvim", "vim", "nano", "vi"])
+    ///     .build();
+    /// ```
+    pub fn fallbacks<S, I>(mut self, fallbacks: I) -> Self
+    where
+        S: AsRef<OsStr>,
+        I: IntoIterator<Item = S>,
+    {
+        self.finder.fallbacks = fallbacks
+            .into_iter()
+            .map(|s| OsString::from(s.as_ref()))
+            .collect();
+        self
+    }
+
+    /// Sets whether git's editor should be looked up after the environment variables,
+    /// and before the fallback.
+    ///
+    /// Git's editor is defined by `$GIT_EDITOR`, or by the `core.editor` option in
+    /// `git config`.
+    ///
+    /// This is the same as adding or removing [`Source::Git`].
+    #[cfg(feature = "git")]
+    #[inline]
+    pub fn git(self, git: bool) -> Self {
+        self.toggle_source(Source::Git, git)
+    }
+
+    /// Sets whether editors that suit the current context should be preferred. This
+    /// defaults to `false`.
+    ///
+    /// When `true`, every source is searched, and the first editor that suits the
+    /// context is picked:
+    ///
+    /// - Without a display (e.g. in an SSH session without `$DISPLAY` or
+    ///   `$WAYLAND_DISPLAY`), a terminal editor is preferred, even if `$VISUAL` is a
+    ///   graphical editor.
+    /// - Without a terminal (e.g. a graphical application, or when piped), a
+    ///   graphical editor is preferred.
+    ///
+    /// Editors are recognized with [`EditorInfo`](crate::EditorInfo). If no editor
+    /// suits the context, the first editor found is used.
+    pub fn prefer_context_appropriate(mut self, prefer_context: bool) -> Self {
+        self.finder.prefer_context = prefer_context;
+        self
+    }
+
+    /// Sets whether `$VISUAL` should only be used on a capable terminal. This defaults
+    /// to `false`.
+    ///
+    /// Historically, `$VISUAL` is a full-screen editor that needs a capable terminal,
+    /// and `$EDITOR` is a line editor that works anywhere. When `true`, `$VISUAL` is
+    /// skipped unless stdout is a terminal, and `$TERM` has a terminfo entry and isn't
+    /// `dumb`.
+    pub fn posix_visual(mut self, posix_visual: bool) -> Self {
+        self.finder.posix_visual = posix_visual;
+        self
+    }
+
+    /// Sets whether VS Code should be fallen back to in VS Code's integrated terminal.
+    /// This defaults to `false`.
+    ///
+    /// When `true` and no editor is configured, `code --wait` is used instead of the
+    /// fallbacks if `$TERM_PROGRAM` is `vscode` or `$VSCODE_GIT_IPC_HANDLE` is set,
+    /// because the user is already in VS Code. With the `which` feature, `code` must
+    /// also be on `$PATH`.
+    pub fn vscode_terminal(mut self, vscode_terminal: bool) -> Self {
+        self.finder.vscode_terminal = vscode_terminal;
+        self
+    }
+
+    /// Sets the rules used to split an editor into its command and arguments. This
+    /// defaults to [`SplitStyle::Windows`] on Windows, and [`SplitStyle::Posix`] on
+    /// every other platform.
+    #[cfg(feature = "split")]
+    pub fn split_style(mut self, style: SplitStyle) -> Self {
+        self.finder.split_style = style;
+        self
+    }
+
+    /// Sets whether editors that can't be found on `$PATH` should be skipped. This
+    /// defaults to `false`.
+    ///
+    /// When `true`, an editor whose command can't be found (e.g. a stale `EDITOR=subl`)
+    /// is skipped, and the search continues with the next editor, and then the
+    /// fallbacks. When `false`, [`Finder::which_editor`] returns
+    /// [`Error::EditorNotFound`] for such an editor.
+    ///
+    /// [`Error::EditorNotFound`]: crate::Error::EditorNotFound
+    #[cfg(feature = "which")]
+    pub fn skip_missing_editors(mut self, skip_missing: bool) -> Self {
+        self.finder.skip_missing = skip_missing;
+        self
+    }
+
+    /// Sets whether an editor's command can be a relative path, like `./bin/edit`. This
+    /// defaults to `true`.
+    ///
+    /// Relative paths are resolved from the current directory, which may not be
+    /// trusted. When `false`, [`Finder::which_editor`] returns
+    /// [`Error::RelativeEditorPath`] for such an editor. Bare commands, like `vim`,
+    /// are still searched for on `$PATH`.
+    ///
+    /// [`Error::RelativeEditorPath`]: crate::Error::RelativeEditorPath
+    #[cfg(feature = "which")]
+    pub fn relative_editor_paths(mut self, allow: bool) -> Self {
+        self.finder.allow_relative_paths = allow;
+        self
+    }
+
+    /// Sets whether commands that can't be found on `$PATH` should be found with the
+    /// user's login shell. This defaults to `false`, and has no effect on platforms
+    /// other than Unix.
+    ///
+    /// When `true`, `$SHELL -lic 'command -v <command>'` finds commands that only exist
+    /// after the shell's init files run, like shims from version managers. This runs
+    /// the shell, which can be slow, each time a command isn't found, and the shell is
+    /// killed if it takes longer than 5 seconds. Consider [`Finder::cached`] to only
+    /// find the editor once.
+    #[cfg(feature = "which")]
+    pub fn login_shell(mut self, login_shell: bool) -> Self {
+        self.finder.login_shell = login_shell;
+        self
+    }
+
+    /// Sets the `$PATH` that commands are found on, instead of the environment's
+    /// `$PATH`. This is a list of directories separated like `$PATH`, with `:` on Unix
+    /// and `;` on Windows.
+    ///
+    /// This makes finding commands hermetic, like in tests or sandboxes, so commands
+    /// that aren't on this path aren't found with [`FinderBuilder::login_shell`] or in
+    /// Windows' app execution aliases either. It doesn't change the `$PATH` of the
+    /// editor's process. See [`FinderBuilder::search_dirs`] to set a list of
+    /// directories.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::builder().search_path("/usr/local/bin:/usr/bin").build();
+    /// ```
+    #[cfg(feature = "which")]
+    pub fn search_path<S>(mut self, path: S) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.finder.search_path = Some(path.as_ref().into());
+        self
+    }
+
+    /// Sets the directories that commands are found in, in order, instead of the
+    /// environment's `$PATH`.
+    ///
+    /// Directories that contain the `$PATH` separator can't be searched, so they're
+    /// skipped. See [`FinderBuilder::search_path`] for more information.
+    #[cfg(feature = "which")]
+    pub fn search_dirs<P, I>(mut self, dirs: I) -> Self
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = P>,
+    {
+        let dirs = dirs
+            .into_iter()
+            .map(|dir| dir.as_ref().to_path_buf())
+            .filter(|dir| std::env::join_paths([dir]).is_ok())
+            .collect::<Vec<_>>();
+        let path = std::env::join_paths(dirs).expect("Directories should be joinable");
+        self.finder.search_path = Some(path);
+        self
+    }
+
+    /// Sets whether `$SUDO_EDITOR` should be looked up before any other source, like
+    /// `sudoedit` does.
+    ///
+    /// This is useful for tools that edit files with elevated privileges. This is the
+    /// same as adding or removing [`Source::Sudo`].
+    #[inline]
+    pub fn sudo_editor(self, sudo_editor: bool) -> Self {
+        self.toggle_source(Source::Sudo, sudo_editor)
+    }
+
+    /// Sets whether Debian's editor conventions should be followed before falling back.
+    ///
+    /// This looks up the editor chosen with `select-editor` in `~/.selected_editor`,
+    /// and then `/usr/bin/editor`, which is managed by `update-alternatives`. This is
+    /// what `sensible-editor` does, and has no effect on platforms other than Unix.
+    ///
+    /// This is the same as adding or removing [`Source::Debian`].
+    #[inline]
+    pub fn debian(self, debian: bool) -> Self {
+        self.toggle_source(Source::Debian, debian)
+    }
+
+    /// Sets whether the default text editor should be looked up in the Windows
+    /// registry before falling back.
+    ///
+    /// This uses the program associated with `.txt` files. It has no effect on
+    /// platforms other than Windows.
+    ///
+    /// This is the same as adding or removing [`Source::WindowsRegistry`].
+    #[cfg(feature = "windows-registry")]
+    #[inline]
+    pub fn windows_registry(self, windows_registry: bool) -> Self {
+        self.toggle_source(Source::WindowsRegistry, windows_registry)
+    }
+
+    /// Sets the sources to search for an editor, in order.
+    ///
+    /// Sources that aren't listed are not searched. The fallback editor is always used
+    /// after every source has been searched. The default sources are
+    /// [`Source::ExtraEnv`], [`Source::Custom`], and [`Source::StandardEnv`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::{Finder, Source};
+    /// use std::ffi::OsString;
+    ///
+    /// // Prefer the editor from the config file over any environment variable.
+    /// let finder = Finder::builder()
+    ///     .resolver(|| Some(OsString::from("nano")))
+    ///     .sources([Source::Custom, Source::ExtraEnv, Source::StandardEnv])
+    ///     .build();
+    /// ```
+    pub fn sources<I>(mut self, sources: I) -> Self
+    where
+        I: IntoIterator<Item = Source>,
+    {
+        self.finder.sources = sources.into_iter().collect();
+        self
+    }
+
+    /// Adds a source in its default position if `enabled`, or removes it otherwise.
+    fn toggle_source(mut self, source: Source, enabled: bool) -> Self {
+        let sources = &mut self.finder.sources;
+        if !enabled {
+            sources.retain(|s| *s != source);
+        } else if !sources.contains(&source) {
+            let index = sources
+                .iter()
+                .position(|s| *s > source)
+                .unwrap_or(sources.len());
+            sources.insert(index, source);
+        }
+        self
+    }
+
+    /// Builds the [`Finder`].
+    #[inline]
+    pub fn build(self) -> Finder {
+        self.finder
+    }
+}
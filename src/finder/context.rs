@@ -0,0 +1,204 @@
+//! Utilities for picking an editor that suits the current context.
+use super::ResolvedEditor;
+use crate::EditorInfo;
+use std::ffi::{OsStr, OsString};
+
+/// The kind of editor that suits the current context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Context {
+    /// There's no display, so a terminal editor is preferred.
+    Terminal,
+    /// There's no terminal, so a graphical editor is preferred.
+    Gui,
+    /// Any editor is suitable.
+    Any,
+}
+
+impl Context {
+    /// Detects the current context.
+    pub(super) fn detect<Env>(f: Env) -> Self
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        use std::io::{self, IsTerminal};
+
+        let has_terminal = io::stdin().is_terminal() || io::stdout().is_terminal();
+        Self::new(has_display(f), has_terminal)
+    }
+
+    /// Gets the context for whether there's a display and a terminal.
+    fn new(has_display: bool, has_terminal: bool) -> Self {
+        match (has_display, has_terminal) {
+            (false, _) => Self::Terminal,
+            (true, false) => Self::Gui,
+            (true, true) => Self::Any,
+        }
+    }
+
+    /// Picks the first editor that suits this context, or else the first editor.
+    pub(super) fn pick<I>(self, candidates: I) -> Option<ResolvedEditor>
+    where
+        I: IntoIterator<Item = ResolvedEditor>,
+    {
+        let mut candidates = candidates.into_iter();
+        if self == Self::Any {
+            return candidates.next();
+        }
+        let candidates = candidates.collect::<Vec<_>>();
+        let index = candidates
+            .iter()
+            .position(|candidate| self.suits(candidate.name()))
+            .unwrap_or(0);
+        candidates.into_iter().nth(index)
+    }
+
+    /// Checks if an editor is known to suit this context.
+    fn suits(self, editor: &OsStr) -> bool {
+        let Some(info) = editor_info(editor) else {
+            return false;
+        };
+        match self {
+            Self::Terminal => !info.gui,
+            Self::Gui => info.gui,
+            Self::Any => true,
+        }
+    }
+}
+
+/// Checks if the terminal is capable enough for `$VISUAL`: it's a TTY, and `$TERM` has
+/// a terminfo entry that isn't `dumb`.
+pub(super) fn has_capable_terminal<Env>(mut f: Env) -> bool
+where
+    Env: FnMut(&OsStr) -> Option<OsString>,
+{
+    use std::io::{self, IsTerminal};
+
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+    // NOTE Windows terminals don't use terminfo.
+    if cfg!(windows) {
+        return true;
+    }
+    let Some(term) = f(OsStr::new("TERM")) else {
+        return false;
+    };
+    is_capable_term(&term) && has_terminfo(&term, f)
+}
+
+/// Checks if the value of `$TERM` describes a capable terminal.
+fn is_capable_term(term: &OsStr) -> bool {
+    !term.is_empty() && term != "dumb"
+}
+
+/// Checks if `term` has an entry in a terminfo database.
+fn has_terminfo<Env>(term: &OsStr, mut f: Env) -> bool
+where
+    Env: FnMut(&OsStr) -> Option<OsString>,
+{
+    use std::path::PathBuf;
+
+    /// The directories terminfo databases are usually in.
+    const SYSTEM_DIRS: [&str; 4] = [
+        "/etc/terminfo",
+        "/lib/terminfo",
+        "/usr/share/terminfo",
+        "/usr/lib/terminfo",
+    ];
+
+    let Some(first) = term.to_string_lossy().chars().next() else {
+        return false;
+    };
+    let mut dirs = Vec::new();
+    dirs.extend(f(OsStr::new("TERMINFO")).map(PathBuf::from));
+    dirs.extend(f(OsStr::new("HOME")).map(|home| PathBuf::from(home).join(".terminfo")));
+    if let Some(terminfo_dirs) = f(OsStr::new("TERMINFO_DIRS")) {
+        dirs.extend(std::env::split_paths(&terminfo_dirs));
+    }
+    dirs.extend(SYSTEM_DIRS.map(PathBuf::from));
+    // NOTE Entries are in a directory named after their first letter, or its hex code
+    //      on macOS.
+    let subdirs = [first.to_string(), format!("{:x}", u32::from(first))];
+    dirs.iter().any(|dir| {
+        subdirs
+            .iter()
+            .any(|subdir| dir.join(subdir).join(term).is_file())
+    })
+}
+
+/// Checks if there's a display that graphical editors can open in.
+fn has_display<Env>(mut f: Env) -> bool
+where
+    Env: FnMut(&OsStr) -> Option<OsString>,
+{
+    // NOTE Windows and macOS always have a display, unless running headless.
+    if cfg!(any(windows, target_os = "macos")) {
+        return true;
+    }
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .into_iter()
+        .any(|key| f(OsStr::new(key)).is_some_and(|value| !value.is_empty()))
+}
+
+/// Finds the metadata for an editor's command, ignoring its arguments.
+fn editor_info(editor: &OsStr) -> Option<&'static EditorInfo> {
+    let editor = editor.to_string_lossy();
+    #[cfg(feature = "split")]
+    let command = shell_words::split(&editor).ok()?.into_iter().next()?;
+    #[cfg(not(feature = "split"))]
+    let command = editor.split_whitespace().next()?.to_string();
+    EditorInfo::for_command(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EditorSource;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::no_display(false, true, Context::Terminal)]
+    #[case::no_terminal(true, false, Context::Gui)]
+    #[case::both(true, true, Context::Any)]
+    fn test_new(#[case] has_display: bool, #[case] has_terminal: bool, #[case] expected: Context) {
+        assert_eq!(expected, Context::new(has_display, has_terminal));
+    }
+
+    #[rstest]
+    #[case::terminal(Context::Terminal, "vim")]
+    #[case::gui(Context::Gui, "code --wait")]
+    #[case::any(Context::Any, "code --wait")]
+    fn test_pick(#[case] context: Context, #[case] expected: &str) {
+        let candidates = ["code --wait", "vim"].map(|name| {
+            let source = EditorSource::StandardEnv(OsString::from("VISUAL"));
+            ResolvedEditor::new(name, source)
+        });
+        let actual = context.pick(candidates).unwrap();
+        assert_eq!(expected, actual.name());
+    }
+
+    #[rstest]
+    #[case::xterm("xterm-256color", true)]
+    #[case::dumb("dumb", false)]
+    #[case::empty("", false)]
+    fn test_is_capable_term(#[case] term: &str, #[case] expected: bool) {
+        assert_eq!(expected, is_capable_term(OsStr::new(term)));
+    }
+
+    #[test]
+    fn test_has_terminfo() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("m")).unwrap();
+        std::fs::write(dir.path().join("m").join("my-term"), "").unwrap();
+        let f = |key: &OsStr| (key == "TERMINFO").then(|| dir.path().as_os_str().to_owned());
+        assert!(has_terminfo(OsStr::new("my-term"), f));
+        assert!(!has_terminfo(OsStr::new("--UNKNOWN--"), f));
+    }
+
+    #[test]
+    fn test_pick_unsuitable() {
+        let candidates = [ResolvedEditor::new("code", EditorSource::Fallback)];
+        let actual = Context::Terminal.pick(candidates).unwrap();
+        assert_eq!("code", actual.name());
+    }
+}
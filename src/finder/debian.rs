@@ -0,0 +1,108 @@
+//! Utilities for following Debian's editor conventions.
+use super::{Finder, ResolvedEditor};
+use std::ffi::{OsStr, OsString};
+
+impl Finder {
+    /// Finds the editor selected with Debian's `select-editor`, or Debian's editor
+    /// alternative.
+    #[cfg(unix)]
+    pub(super) fn find_debian_editor<Env>(&self, mut f: Env) -> Option<ResolvedEditor>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        use super::EditorSource;
+        use std::path::{Path, PathBuf};
+
+        /// Debian's editor alternative, managed by `update-alternatives`.
+        const ALTERNATIVE: &str = "/usr/bin/editor";
+
+        let selected_editor = f(OsStr::new("HOME")).and_then(|home| {
+            let path = PathBuf::from(home).join(".selected_editor");
+            let contents = std::fs::read_to_string(&path).ok()?;
+            let editor = parse_selected_editor(&contents)?;
+            Some(ResolvedEditor::new(
+                editor,
+                EditorSource::SelectedEditor(path),
+            ))
+        });
+        selected_editor.or_else(|| {
+            Path::new(ALTERNATIVE)
+                .is_file()
+                .then(|| ResolvedEditor::new(ALTERNATIVE, EditorSource::DebianAlternatives))
+        })
+    }
+
+    /// Finds Debian's editor. Debian's conventions only apply to Unix.
+    #[cfg(not(unix))]
+    pub(super) fn find_debian_editor<Env>(&self, _f: Env) -> Option<ResolvedEditor>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        None
+    }
+}
+
+/// Parses the `SELECTED_EDITOR` value from the contents of a `.selected_editor` file.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub(super) fn parse_selected_editor(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| line.strip_prefix("SELECTED_EDITOR="))
+        .map(unquote)
+        .filter(|value| !value.is_empty())
+}
+
+/// Removes the shell quotes from a `value`, like `"/usr/bin/vim"` or `'it'\''s'`. The
+/// file is sourced by a shell, so its quotes follow the shell's rules.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn unquote(value: &str) -> String {
+    let mut unquoted = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => unquoted.extend(chars.by_ref().take_while(|&c| c != '\'')),
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        // NOTE In double quotes, a backslash only escapes these.
+                        '\\' => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => unquoted.push(c),
+                            Some(c) => unquoted.extend(['\\', c]),
+                            None => unquoted.push('\\'),
+                        },
+                        c => unquoted.push(c),
+                    }
+                }
+            }
+            '\\' => unquoted.extend(chars.next()),
+            c => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::quoted(
+        "# Generated by /usr/bin/select-editor\nSELECTED_EDITOR=\"/usr/bin/vim.basic\"\n",
+        Some("/usr/bin/vim.basic")
+    )]
+    #[case::unquoted("SELECTED_EDITOR=/bin/nano", Some("/bin/nano"))]
+    #[case::commented("# SELECTED_EDITOR=\"/bin/nano\"", None)]
+    #[case::empty("SELECTED_EDITOR=\"\"", None)]
+    #[case::single_quoted(
+        r#"SELECTED_EDITOR='vim -c '\''echo "$HOME"'\'''"#,
+        Some(r#"vim -c 'echo "$HOME"'"#)
+    )]
+    #[case::escaped(r#"SELECTED_EDITOR="my \"editor\" \$1""#, Some(r#"my "editor" $1"#))]
+    fn test_parse_selected_editor(#[case] contents: &str, #[case] expected: Option<&str>) {
+        assert_eq!(expected, parse_selected_editor(contents).as_deref());
+    }
+}
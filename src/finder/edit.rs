@@ -0,0 +1,236 @@
+//! Utilities for editing content in a temporary file.
+use super::Finder;
+use crate::Error;
+#[cfg(feature = "serde")]
+use crate::{EditError, Format};
+
+impl Finder {
+    /// Edits `initial` in an editor, and returns the edited text.
+    ///
+    /// The text is written to a new temporary file, which is opened in the editor. After
+    /// the editor is closed, the file is read and deleted. Use [`Finder::edit_builder`]
+    /// to choose the file's extension.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let message = finder
+    ///     .edit_string("# Write your message here\n")
+    ///     .expect("Should be able to edit the message");
+    /// ```
+    pub fn edit_string(&self, initial: &str) -> Result<String, Error> {
+        self.edit_builder(initial).edit()
+    }
+
+    /// Edits `initial` in an editor without blocking the async runtime, and returns the
+    /// edited text.
+    ///
+    /// See [`OpenOptions::open_async`](super::OpenOptions::open_async) for the runtimes
+    /// this supports, and [`Finder::edit_string`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// # async fn run() {
+    /// let finder = Finder::new();
+    /// let message = finder
+    ///     .edit_string_async("# Write your message here\n")
+    ///     .await
+    ///     .expect("Should be able to edit the message");
+    /// # }
+    /// ```
+    #[cfg(any(feature = "async-process", feature = "tokio"))]
+    pub async fn edit_string_async(&self, initial: &str) -> Result<String, Error> {
+        self.edit_builder(initial).edit_async().await
+    }
+
+    /// Edits `initial` in an editor, and returns the edited bytes.
+    ///
+    /// Unlike [`Finder::edit_string`], the content doesn't need to be valid UTF-8, so
+    /// content with an unknown encoding is never converted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let edited = finder
+    ///     .edit_bytes(b"caf\xe9")
+    ///     .expect("Should be able to edit the content");
+    /// ```
+    pub fn edit_bytes(&self, initial: &[u8]) -> Result<Vec<u8>, Error> {
+        self.edit_builder(initial).edit_bytes()
+    }
+
+    /// Edits `value` in an editor as text in the given `format`, and returns the edited
+    /// value.
+    ///
+    /// If the edited text fails to parse, [`EditError::Parse`] contains the text, so
+    /// that it can be edited again instead of losing the user's changes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "toml")]
+    /// # {
+    /// use find_editor::{EditError, Finder, Format};
+    /// use std::collections::BTreeMap;
+    ///
+    /// let finder = Finder::new();
+    /// let config: BTreeMap<String, String> =
+    ///     BTreeMap::from([("name".into(), "find-editor".into())]);
+    /// match finder.edit_serde(&config, Format::Toml) {
+    ///     Ok(config) => println!("{config:?}"),
+    ///     Err(EditError::Parse { text, source, .. }) => eprintln!("{source}:\n{text}"),
+    ///     Err(e) => eprintln!("{e}"),
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn edit_serde<T>(&self, value: &T, format: Format) -> Result<T, EditError>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let text = format.serialize(value).map_err(EditError::Serialize)?;
+        let text = self
+            .edit_builder(text)
+            .extension(format.extension())
+            .edit()?;
+        format
+            .deserialize(&text)
+            .map_err(|source| EditError::Parse { text, source })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_edit_string() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'printf "%s, world" "$(cat "$0")" > "$0"'"#)
+            .build();
+        let actual = finder.edit_string("Hello").unwrap();
+        assert_eq!("Hello, world", actual);
+    }
+
+    #[test]
+    fn test_edit_bytes() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'printf "\377" >> "$0"'"#)
+            .build();
+        let actual = finder.edit_bytes(b"\xe9").unwrap();
+        assert_eq!(b"\xe9\xff", actual.as_slice());
+    }
+
+    #[test]
+    fn test_edit_builder_file_name() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'basename "$0" > "$0"'"#)
+            .build();
+        let actual = finder
+            .edit_builder("")
+            .file_name("COMMIT_EDITMSG")
+            .edit()
+            .unwrap();
+        assert_eq!("COMMIT_EDITMSG\n", actual);
+    }
+
+    #[test]
+    fn test_edit_builder_extension() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'printf "%s" "${0##*.}" > "$0"'"#)
+            .build();
+        let actual = finder.edit_builder("").extension("toml").edit().unwrap();
+        assert_eq!("toml", actual);
+    }
+
+    #[rstest]
+    #[case::unchanged("true", None)]
+    #[case::changed(r#"sh -c 'printf "!" >> "$0"'"#, Some("Hello!"))]
+    fn test_edit_if_changed(#[case] editor: &str, #[case] expected: Option<&str>) {
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let actual = finder.edit_builder("Hello").edit_if_changed().unwrap();
+        assert_eq!(expected, actual.as_deref());
+    }
+
+    #[cfg(feature = "json")]
+    #[rstest]
+    #[case::valid(r#"{"name": "edited"}"#, Ok("edited"))]
+    #[case::invalid("{", Err("{"))]
+    fn test_edit_serde(#[case] edited: &str, #[case] expected: Result<&str, &str>) {
+        use std::collections::BTreeMap;
+
+        let editor = format!(r#"sh -c 'printf "%s" "$0" > "$1"' '{edited}'"#);
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let value = BTreeMap::from([(String::from("name"), String::from("initial"))]);
+        let actual: Result<BTreeMap<String, String>, _> = finder.edit_serde(&value, Format::Json);
+        let actual = match actual {
+            Ok(value) => Ok(value["name"].clone()),
+            Err(EditError::Parse { text, .. }) => Err(text),
+            Err(e) => panic!("{e}"),
+        };
+        assert_eq!(
+            expected,
+            actual.as_ref().map(String::as_str).map_err(String::as_str)
+        );
+    }
+
+    #[test]
+    fn test_edit_validated_retry() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'grep -q "^# " "$0" && n=42 || n=nope; echo $n > "$0"'"#)
+            .build();
+        let actual = finder
+            .edit_builder("")
+            .edit_validated(|text| text.trim().parse::<u32>())
+            .unwrap();
+        assert_eq!(Ok(42), actual);
+    }
+
+    #[test]
+    fn test_edit_validated_max_retries() {
+        let finder = Finder::builder().sources([]).fallback("true").build();
+        let mut attempts = Vec::new();
+        let actual = finder
+            .edit_builder("nope")
+            .max_retries(1)
+            .edit_validated(|text| {
+                attempts.push(text.to_string());
+                text.parse::<u32>()
+            });
+        assert!(actual.unwrap().is_err());
+        assert_eq!(["nope", "nope"], attempts.as_slice());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_edit_string_async() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'printf "%s, world" "$(cat "$0")" > "$0"'"#)
+            .build();
+        let actual = finder.edit_string_async("Hello").await.unwrap();
+        assert_eq!("Hello, world", actual);
+    }
+
+    #[test]
+    fn test_edit_builder_fail_on_nonzero() {
+        let finder = Finder::builder().sources([]).fallback("false").build();
+        let actual = finder.edit_builder("").fail_on_nonzero(true).edit();
+        assert!(matches!(actual, Err(Error::EditorExited { .. })));
+    }
+}
@@ -0,0 +1,342 @@
+//! Contains the [`EditBuilder`] struct for configuring how content is edited.
+use super::{Finder, OpenOptions};
+use crate::Error;
+use std::ffi::{OsStr, OsString};
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Builder for editing content in a temporary file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use find_editor::EditBuilder;
+///
+/// let config = EditBuilder::new("[package]\n")
+///     .extension("toml")
+///     .edit()
+///     .expect("Should be able to edit the config");
+/// ```
+pub struct EditBuilder<'a> {
+    /// The finder used to find the editor, or the default finder.
+    finder: Option<&'a Finder>,
+    /// The content to edit.
+    content: Vec<u8>,
+    /// How the temporary file is named.
+    name: TempName,
+    /// The prefix of the comments that explain why content was rejected.
+    comment_prefix: String,
+    /// How many times the editor is reopened when content is rejected.
+    max_retries: usize,
+    /// If a non-zero exit status is an error.
+    fail_on_nonzero: bool,
+    /// If `SIGINT` and `SIGQUIT` are ignored while waiting.
+    ignore_signals: bool,
+    /// If the terminal's settings are restored after the editor exits.
+    restore_terminal: bool,
+}
+
+/// How a temporary file is named.
+enum TempName {
+    /// A random name with an extension.
+    Extension(OsString),
+    /// An exact file name, in a random temporary directory.
+    FileName(OsString),
+}
+
+impl Finder {
+    /// Creates an [`EditBuilder`] to configure how `content` is edited.
+    #[inline]
+    pub fn edit_builder<C>(&self, content: C) -> EditBuilder<'_>
+    where
+        C: Into<Vec<u8>>,
+    {
+        EditBuilder {
+            finder: Some(self),
+            ..EditBuilder::new(content)
+        }
+    }
+}
+
+impl EditBuilder<'_> {
+    /// Creates a new [`EditBuilder`] to edit `content` with the default [`Finder`].
+    pub fn new<C>(content: C) -> Self
+    where
+        C: Into<Vec<u8>>,
+    {
+        Self {
+            finder: None,
+            content: content.into(),
+            name: TempName::Extension(OsString::from("txt")),
+            comment_prefix: String::from("#"),
+            max_retries: 3,
+            fail_on_nonzero: false,
+            ignore_signals: false,
+            restore_terminal: false,
+        }
+    }
+
+    /// Sets the temporary file's extension, without the leading `.`. This defaults to
+    /// `txt`.
+    ///
+    /// Editors often use the extension to pick syntax highlighting.
+    pub fn extension<S>(mut self, extension: S) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.name = TempName::Extension(extension.as_ref().into());
+        self
+    }
+
+    /// Sets the temporary file's exact name, like `COMMIT_EDITMSG`. The file is created
+    /// in a new temporary directory.
+    ///
+    /// Editors often use the file name to pick syntax highlighting and plugins.
+    pub fn file_name<S>(mut self, file_name: S) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.name = TempName::FileName(file_name.as_ref().into());
+        self
+    }
+
+    /// Sets the prefix of the comments that explain why content was rejected by
+    /// [`EditBuilder::edit_validated`], like `//`. This defaults to `#`.
+    pub fn comment_prefix<S>(mut self, comment_prefix: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.comment_prefix = comment_prefix.into();
+        self
+    }
+
+    /// Sets how many times [`EditBuilder::edit_validated`] reopens the editor when the
+    /// content is rejected. This defaults to 3.
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets whether the editor exiting with a non-zero exit status is an error. This
+    /// defaults to `false`.
+    ///
+    /// See [`OpenOptions::fail_on_nonzero`] for more information.
+    pub fn fail_on_nonzero(mut self, fail_on_nonzero: bool) -> Self {
+        self.fail_on_nonzero = fail_on_nonzero;
+        self
+    }
+
+    /// Sets whether to ignore `SIGINT` and `SIGQUIT` while waiting for the editor. This
+    /// defaults to `false`.
+    ///
+    /// See [`OpenOptions::ignore_signals`] for more information.
+    pub fn ignore_signals(mut self, ignore_signals: bool) -> Self {
+        self.ignore_signals = ignore_signals;
+        self
+    }
+
+    /// Sets whether to restore the terminal's settings after the editor exits. This
+    /// defaults to `false`.
+    ///
+    /// See [`OpenOptions::restore_terminal`] for more information.
+    pub fn restore_terminal(mut self, restore_terminal: bool) -> Self {
+        self.restore_terminal = restore_terminal;
+        self
+    }
+
+    /// Edits the content in an editor, and validates the edited text with `validate`.
+    ///
+    /// If `validate` returns an error, the error is inserted as a comment at the top of
+    /// the text, and the editor is reopened so that the user can fix the text, like
+    /// `visudo` and `kubectl edit`. If the comment is left as-is, it is removed before
+    /// the text is validated again. After the [maximum number of
+    /// retries](EditBuilder::max_retries), the last validation error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::EditBuilder;
+    ///
+    /// let port = EditBuilder::new("8080\n")
+    ///     .edit_validated(|text| text.trim().parse::<u16>())
+    ///     .expect("Should be able to edit the port");
+    /// match port {
+    ///     Ok(port) => println!("Listening on {port}"),
+    ///     Err(e) => eprintln!("Invalid port: {e}"),
+    /// }
+    /// ```
+    pub fn edit_validated<T, E, F>(self, mut validate: F) -> Result<Result<T, E>, Error>
+    where
+        E: Display,
+        F: FnMut(&str) -> Result<T, E>,
+    {
+        let mut edited = into_string(self.edit_content(&self.content)?)?;
+        let mut retries = 0;
+        loop {
+            let e = match validate(&edited) {
+                Ok(value) => return Ok(Ok(value)),
+                Err(e) if retries >= self.max_retries => return Ok(Err(e)),
+                Err(e) => e,
+            };
+            retries += 1;
+            let comment = self.error_comment(&e);
+            let content = format!("{comment}{edited}");
+            edited = into_string(self.edit_content(content.as_bytes())?)?;
+            if edited.starts_with(&comment) {
+                edited.replace_range(..comment.len(), "");
+            }
+        }
+    }
+
+    /// Edits the content in an editor, and returns the edited text.
+    pub fn edit(self) -> Result<String, Error> {
+        into_string(self.edit_bytes()?)
+    }
+
+    /// Like [`EditBuilder::edit`], but doesn't block the async runtime.
+    ///
+    /// See [`OpenOptions::open_async`](super::OpenOptions::open_async) for the runtimes
+    /// this supports.
+    #[cfg(any(feature = "async-process", feature = "tokio"))]
+    pub async fn edit_async(self) -> Result<String, Error> {
+        into_string(self.edit_bytes_async().await?)
+    }
+
+    /// Like [`EditBuilder::edit_bytes`], but doesn't block the async runtime.
+    ///
+    /// See [`OpenOptions::open_async`](super::OpenOptions::open_async) for the runtimes
+    /// this supports.
+    #[cfg(any(feature = "async-process", feature = "tokio"))]
+    pub async fn edit_bytes_async(self) -> Result<Vec<u8>, Error> {
+        self.edit_content_async(&self.content).await
+    }
+
+    /// Edits the content in an editor, and returns the edited text, or `None` if the
+    /// content wasn't changed.
+    ///
+    /// This is useful for treating "closed the editor without changes" as an abort.
+    pub fn edit_if_changed(self) -> Result<Option<String>, Error> {
+        let initial = self.content.clone();
+        let edited = self.edit()?;
+        Ok((edited.as_bytes() != initial).then_some(edited))
+    }
+
+    /// Edits the content in an editor, and returns the edited bytes, or `None` if the
+    /// content wasn't changed.
+    pub fn edit_bytes_if_changed(self) -> Result<Option<Vec<u8>>, Error> {
+        let initial = self.content.clone();
+        let edited = self.edit_bytes()?;
+        Ok((edited != initial).then_some(edited))
+    }
+
+    /// Edits the content in an editor, and returns the edited bytes.
+    pub fn edit_bytes(self) -> Result<Vec<u8>, Error> {
+        self.edit_content(&self.content)
+    }
+
+    /// Comments out an error's message, one comment per line.
+    fn error_comment<E>(&self, e: &E) -> String
+    where
+        E: Display,
+    {
+        e.to_string()
+            .lines()
+            .map(|line| format!("{} {line}\n", self.comment_prefix))
+            .collect()
+    }
+
+    /// Writes `content` to a temporary file, edits it in an editor, and returns the
+    /// edited bytes.
+    fn edit_content(&self, content: &[u8]) -> Result<Vec<u8>, Error> {
+        let file = self.create_temp_file(content)?;
+        match self.finder {
+            Some(finder) => self.open_options(finder).open(file.path())?,
+            None => self.open_options(&Finder::new()).open(file.path())?,
+        };
+        fs::read(file.path()).map_err(Error::Io)
+    }
+
+    /// Like [`EditBuilder::edit_content`], but doesn't block the async runtime.
+    #[cfg(any(feature = "async-process", feature = "tokio"))]
+    async fn edit_content_async(&self, content: &[u8]) -> Result<Vec<u8>, Error> {
+        let file = self.create_temp_file(content)?;
+        match self.finder {
+            Some(finder) => self.open_options(finder).open_async(file.path()).await?,
+            None => {
+                self.open_options(&Finder::new())
+                    .open_async(file.path())
+                    .await?
+            }
+        };
+        // NOTE The temporary file is small, so reading it won't noticeably block.
+        fs::read(file.path()).map_err(Error::Io)
+    }
+
+    /// Creates the options to open the editor with `finder`.
+    fn open_options<'f>(&self, finder: &'f Finder) -> OpenOptions<'f> {
+        finder
+            .open_options()
+            .fail_on_nonzero(self.fail_on_nonzero)
+            .ignore_signals(self.ignore_signals)
+            .restore_terminal(self.restore_terminal)
+    }
+
+    /// Creates the temporary file to edit, containing `content`.
+    fn create_temp_file(&self, content: &[u8]) -> Result<TempFile, Error> {
+        match &self.name {
+            TempName::Extension(extension) => {
+                let mut suffix = OsString::from(".");
+                suffix.push(extension);
+                let mut file = tempfile::Builder::new()
+                    .prefix("find-editor-")
+                    .suffix(&suffix)
+                    .tempfile()
+                    .map_err(Error::Io)?;
+                file.write_all(content).map_err(Error::Io)?;
+                // NOTE The file is closed so that the editor can replace it, but the path
+                //      is still deleted when dropped.
+                Ok(TempFile::Path(file.into_temp_path()))
+            }
+            TempName::FileName(file_name) => {
+                let dir = tempfile::Builder::new()
+                    .prefix("find-editor-")
+                    .tempdir()
+                    .map_err(Error::Io)?;
+                let path = dir.path().join(file_name);
+                let mut file = File::create_new(&path).map_err(Error::Io)?;
+                file.write_all(content).map_err(Error::Io)?;
+                Ok(TempFile::InDir { _dir: dir, path })
+            }
+        }
+    }
+}
+
+/// A temporary file that is deleted when dropped.
+enum TempFile {
+    /// A temporary file with a random name.
+    Path(tempfile::TempPath),
+    /// A file in a temporary directory.
+    InDir {
+        /// The temporary directory, which is kept so that it's deleted when dropped.
+        _dir: tempfile::TempDir,
+        /// The path to the file.
+        path: PathBuf,
+    },
+}
+
+impl TempFile {
+    /// The path to the temporary file.
+    fn path(&self) -> &Path {
+        match self {
+            Self::Path(path) => path,
+            Self::InDir { path, .. } => path,
+        }
+    }
+}
+
+/// Converts edited bytes to a [`String`], failing if they aren't valid UTF-8.
+fn into_string(edited: Vec<u8>) -> Result<String, Error> {
+    String::from_utf8(edited).map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+}
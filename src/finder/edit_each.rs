@@ -0,0 +1,193 @@
+//! Contains [`Finder::edit_each`], which edits several files and reports on each of
+//! them.
+use super::open_options::hash_file;
+use super::{Finder, OpenOptions, packaged};
+use crate::{EditorInfo, Error};
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+
+/// The result of editing one of the files passed to [`Finder::edit_each`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditedFile {
+    /// The file that was edited.
+    pub path: PathBuf,
+    /// The exit status of the editor that edited the file. Files that were opened
+    /// together share the same exit status.
+    pub status: ExitStatus,
+    /// If the file's content changed. See [`OpenOptions::open_changed`].
+    pub changed: bool,
+}
+
+impl Finder {
+    /// Opens an editor to edit each of `files`, waits until the editor is closed, and
+    /// returns what happened to each file, in the same order as `files`.
+    ///
+    /// Terminal editors, and editors that aren't known, are opened for one file at a
+    /// time, so that the files are edited one after another. Known graphical editors
+    /// are opened once with every file. `options` configures the [`OpenOptions`] for
+    /// each time that the editor is opened. [`OpenOptions::wait`] is ignored.
+    ///
+    /// If editing a file fails, like when the editor exits unsuccessfully with
+    /// [`OpenOptions::fail_on_nonzero`], the file's result is the error, and the files
+    /// after it aren't opened. The results of the files that were already edited are
+    /// kept, so there can be fewer results than `files`. If a graphical editor can't
+    /// edit the files that it's opened with, there's a single error for all of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let results =
+    ///     finder.edit_each(["first.txt", "second.txt"], |options| options.fail_on_nonzero(true));
+    /// for result in results {
+    ///     match result {
+    ///         Ok(file) if !file.changed => println!("{} wasn't changed", file.path.display()),
+    ///         Ok(_) => {}
+    ///         Err(e) => eprintln!("Stopped editing: {e}"),
+    ///     }
+    /// }
+    /// ```
+    pub fn edit_each<'a, P, I, F>(
+        &'a self,
+        files: I,
+        mut options: F,
+    ) -> Vec<Result<EditedFile, Error>>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = P>,
+        F: FnMut(OpenOptions<'a>) -> OpenOptions<'a>,
+    {
+        let files = files
+            .into_iter()
+            .map(|file| file.as_ref().to_path_buf())
+            .collect::<Vec<_>>();
+        if !self.is_gui_editor() {
+            let mut edited = Vec::with_capacity(files.len());
+            for path in files {
+                let result = edit_one(options(self.open_options()), path);
+                let failed = result.is_err();
+                edited.push(result);
+                if failed {
+                    break;
+                }
+            }
+            return edited;
+        }
+        let options = options(self.open_options()).wait(true);
+        let file_paths = files
+            .iter()
+            .map(|file| options.file_path(file))
+            .collect::<Vec<_>>();
+        let before = file_paths
+            .iter()
+            .map(|file| hash(file))
+            .collect::<Result<Vec<_>, _>>();
+        let before = match before {
+            Ok(before) => before,
+            Err(e) => return vec![Err(e)],
+        };
+        let status = match options.open_many(&files) {
+            Ok(status) => status.expect("The editor should have been waited for"),
+            Err(e) => return vec![Err(e)],
+        };
+        files
+            .into_iter()
+            .zip(file_paths)
+            .zip(before)
+            .map(|((path, file_path), before)| {
+                let changed = before != hash(&file_path)?;
+                Ok(EditedFile {
+                    path,
+                    status,
+                    changed,
+                })
+            })
+            .collect()
+    }
+
+    /// Checks if the editor is a known graphical editor.
+    fn is_gui_editor(&self) -> bool {
+        let Ok((editor, args)) = self.split_editor_name_os() else {
+            return false;
+        };
+        let editor = Path::new(&editor);
+        let identity = packaged::command(editor, &args).unwrap_or(editor.as_os_str());
+        EditorInfo::for_command(identity).is_some_and(|info| info.gui)
+    }
+}
+
+/// Opens the editor with `options` to edit the file at `path`, and waits until it's
+/// closed.
+fn edit_one(options: OpenOptions<'_>, path: PathBuf) -> Result<EditedFile, Error> {
+    let options = options.wait(true);
+    let file_path = options.file_path(&path);
+    let before = hash(&file_path)?;
+    let status = options.open(&path)?;
+    let status = status.expect("The editor should have been waited for");
+    let changed = before != hash(&file_path)?;
+    Ok(EditedFile {
+        path,
+        status,
+        changed,
+    })
+}
+
+/// Hashes a file's content, like [`hash_file`].
+fn hash(file: &Path) -> Result<Option<u64>, Error> {
+    hash_file(file).map_err(Error::Io)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_each() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.txt");
+        let second = dir.path().join("-second.txt");
+        std::fs::write(&first, "first").unwrap();
+        std::fs::write(&second, "second").unwrap();
+        let editor = r#"sh -c 'case "$0" in *second*) echo changed >> "$0";; esac'"#;
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let edited = finder.edit_each([&first, &second], |options| options);
+        let actual = edited
+            .iter()
+            .map(|file| file.as_ref().unwrap())
+            .map(|file| (file.path.as_path(), file.status.success(), file.changed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            [
+                (first.as_path(), true, false),
+                (second.as_path(), true, true)
+            ],
+            actual.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_edit_each_current_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "notes").unwrap();
+        let editor = r#"sh -c 'echo changed >> "$0"'"#;
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let edited = finder.edit_each(["notes.txt"], |options| options.current_dir(dir.path()));
+        assert!(edited[0].as_ref().unwrap().changed);
+    }
+
+    #[test]
+    fn test_edit_each_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = ["first.txt", "second.txt", "third.txt"].map(|file| dir.path().join(file));
+        let editor = r#"sh -c 'echo changed >> "$0"; case "$0" in *second*) exit 1;; esac'"#;
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let edited = finder.edit_each(&files, |options| options.fail_on_nonzero(true));
+        assert_eq!(2, edited.len());
+        assert!(edited[0].as_ref().unwrap().changed);
+        assert!(matches!(edited[1], Err(Error::EditorExited { .. })));
+        assert!(!files[2].exists());
+    }
+}
@@ -0,0 +1,169 @@
+//! Utilities for opening files in a running Emacs server with `emacsclient`.
+use super::Finder;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// Adjusts an Emacs `editor` and its `args` to use an Emacs server.
+///
+/// `emacs` is replaced with `emacsclient` if a server is running, unless it has
+/// arguments other than `-nw`. `emacsclient` starts a server with `-a ""` if one isn't
+/// running, and doesn't wait with `-n` if `wait` is `false`.
+pub(super) fn use_server(
+    finder: &Finder,
+    editor: &mut PathBuf,
+    args: &mut Vec<OsString>,
+    wait: bool,
+) {
+    let is_emacs = |editor: &Path, name: &str| {
+        editor
+            .file_stem()
+            .is_some_and(|stem| stem.eq_ignore_ascii_case(name))
+    };
+    if is_emacs(editor, "emacs") && args.iter().all(|arg| arg == "-nw") && is_server_running(finder)
+    {
+        *editor = emacsclient(finder, editor);
+    }
+    if !is_emacs(editor, "emacsclient") {
+        return;
+    }
+    let has_alternate = finder.var_os(OsStr::new("ALTERNATE_EDITOR")).is_some();
+    *args = emacsclient_args(args, wait, has_alternate);
+}
+
+/// Adds the arguments that start a server if one isn't running, and that don't wait if
+/// `wait` is `false`, to `emacsclient`'s `args`. A server isn't started if
+/// `has_alternate` is `true`, because an alternate editor was chosen.
+fn emacsclient_args(args: &[OsString], wait: bool, has_alternate: bool) -> Vec<OsString> {
+    let has_arg = |f: fn(&str) -> bool| args.iter().any(|arg| f(&arg.to_string_lossy()));
+    let mut new_args = Vec::new();
+    // NOTE The alternate editor can be attached, like -avim or --alternate-editor=vim.
+    let is_alternate = |arg: &str| arg.starts_with("--alternate-editor") || arg.starts_with("-a");
+    if !has_alternate && !has_arg(is_alternate) {
+        new_args.extend([OsString::from("-a"), OsString::new()]);
+    }
+    if !wait && !has_arg(|arg| arg == "-n" || arg == "--no-wait") {
+        new_args.push(OsString::from("-n"));
+    }
+    new_args.extend(args.iter().cloned());
+    new_args
+}
+
+/// Finds `emacsclient`, preferring the one installed next to `emacs`.
+pub(super) fn emacsclient(finder: &Finder, emacs: &Path) -> PathBuf {
+    let mut name = OsString::from("emacsclient");
+    if let Some(extension) = emacs.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+    let sibling = emacs.with_file_name(&name);
+    if emacs
+        .parent()
+        .is_some_and(|dir| !dir.as_os_str().is_empty())
+        && sibling.is_file()
+    {
+        return sibling;
+    }
+    #[cfg(feature = "which")]
+    if let Ok(path) = finder.which(OsStr::new("emacsclient")) {
+        return path;
+    }
+    #[cfg(not(feature = "which"))]
+    let _ = finder;
+    PathBuf::from(name)
+}
+
+/// Checks if an Emacs server is running, by connecting to its socket, or by finding its
+/// server file if it uses TCP.
+pub(super) fn is_server_running(finder: &Finder) -> bool {
+    let var = |key: &str| {
+        finder
+            .var_os(OsStr::new(key))
+            .filter(|value| !value.is_empty())
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::net::UnixStream;
+
+        let uid = current_uid();
+        let sockets = [
+            var("EMACS_SOCKET_NAME").map(PathBuf::from),
+            var("XDG_RUNTIME_DIR").map(|dir| Path::new(&dir).join("emacs/server")),
+            Some(
+                var("TMPDIR")
+                    .map_or_else(|| PathBuf::from("/tmp"), PathBuf::from)
+                    .join(format!("emacs{uid}/server")),
+            ),
+        ];
+        if sockets
+            .iter()
+            .flatten()
+            .any(|socket| UnixStream::connect(socket).is_ok())
+        {
+            return true;
+        }
+    }
+    let config_dir = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".config")));
+    let server_files = [
+        var("EMACS_SERVER_FILE").map(PathBuf::from),
+        config_dir.map(|dir| dir.join("emacs/server/server")),
+        var("HOME").map(|home| Path::new(&home).join(".emacs.d/server/server")),
+    ];
+    server_files.iter().flatten().any(|file| file.is_file())
+}
+
+/// Gets the current user's ID.
+#[cfg(unix)]
+fn current_uid() -> libc::uid_t {
+    // SAFETY: getuid is always successful.
+    unsafe { libc::getuid() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::wait(&[], true, false, &["-a", ""])]
+    #[case::no_wait(&["-t"], false, false, &["-a", "", "-n", "-t"])]
+    #[case::alternate_env(&[], true, true, &[])]
+    #[case::alternate_arg(&["-avim", "-n"], false, false, &["-avim", "-n"])]
+    #[case::tty(&["-nw"], false, true, &["-n", "-nw"])]
+    #[case::long_args(&["--alternate-editor=vim", "--no-wait"], false, false, &["--alternate-editor=vim", "--no-wait"])]
+    fn test_emacsclient_args(
+        #[case] args: &[&str],
+        #[case] wait: bool,
+        #[case] has_alternate: bool,
+        #[case] expected: &[&str],
+    ) {
+        let args = args.iter().map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(expected, emacsclient_args(&args, wait, has_alternate));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_use_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("server");
+        let dir_var = dir.path().to_path_buf();
+        let env = [
+            ("EMACS_SOCKET_NAME", socket.clone()),
+            ("XDG_RUNTIME_DIR", dir_var.clone()),
+            ("TMPDIR", dir_var),
+        ]
+        .into_iter()
+        .collect::<crate::EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let mut editor = PathBuf::from("emacs");
+        let mut args = Vec::new();
+        use_server(&finder, &mut editor, &mut args, true);
+        assert_eq!(PathBuf::from("emacs"), editor);
+
+        let _listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+        use_server(&finder, &mut editor, &mut args, true);
+        assert_eq!(Some(OsStr::new("emacsclient")), editor.file_name());
+        assert_eq!(["-a", ""], args.as_slice());
+    }
+}
@@ -0,0 +1,159 @@
+//! Contains the [`EnvSource`] trait for reading environment variables.
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::{OsStr, OsString};
+use std::hash::BuildHasher;
+
+/// A source of environment variables for a [`Finder`](super::Finder).
+///
+/// By default, a [`Finder`](super::Finder) reads the process's environment with
+/// [`ProcessEnv`]. Use another source with [`Finder::with_env_source`] or
+/// [`FinderBuilder::env_source`] to test code that finds an editor without changing
+/// the process's environment. Maps of [`OsString`]s and closures that take an
+/// `&OsStr` and return an `Option<OsString>` are also sources.
+///
+/// [`Finder::with_env_source`]: super::Finder::with_env_source
+/// [`FinderBuilder::env_source`]: super::FinderBuilder::env_source
+///
+/// # Example
+///
+/// ```rust
+/// use find_editor::Finder;
+/// use std::collections::HashMap;
+/// use std::ffi::OsString;
+///
+/// let env = HashMap::from([(OsString::from("EDITOR"), OsString::from("nano"))]);
+/// let finder = Finder::with_env_source(env);
+/// assert_eq!("nano", finder.editor_name());
+/// ```
+pub trait EnvSource: Send + Sync {
+    /// Gets the value of the environment variable `key`, or `None` if it isn't set.
+    fn var_os(&self, key: &OsStr) -> Option<OsString>;
+}
+
+/// Reads the process's environment variables with [`std::env::var_os`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessEnv;
+
+impl EnvSource for ProcessEnv {
+    #[inline]
+    fn var_os(&self, key: &OsStr) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+}
+
+/// A copy of the process's environment variables, taken at one point in time.
+///
+/// Unlike [`ProcessEnv`], finding an editor with a snapshot always has the same result,
+/// even if the environment is changed later. Create a [`Finder`](super::Finder) that
+/// uses one with [`Finder::from_env_snapshot`](super::Finder::from_env_snapshot).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnvSnapshot {
+    /// The environment variables. On Windows, the keys are uppercase.
+    vars: BTreeMap<OsString, OsString>,
+}
+
+impl EnvSnapshot {
+    /// Copies the process's environment variables.
+    pub fn capture() -> Self {
+        std::env::vars_os().collect()
+    }
+
+    /// Normalizes a key. Windows environment variables are case-insensitive.
+    fn normalize(key: &OsStr) -> OsString {
+        if cfg!(windows) {
+            key.to_ascii_uppercase()
+        } else {
+            key.to_owned()
+        }
+    }
+}
+
+impl EnvSource for EnvSnapshot {
+    #[inline]
+    fn var_os(&self, key: &OsStr) -> Option<OsString> {
+        self.vars.get(&Self::normalize(key)).cloned()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for EnvSnapshot
+where
+    K: AsRef<OsStr>,
+    V: Into<OsString>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let vars = iter
+            .into_iter()
+            .map(|(key, value)| (Self::normalize(key.as_ref()), value.into()))
+            .collect();
+        Self { vars }
+    }
+}
+
+impl<F> EnvSource for F
+where
+    F: Fn(&OsStr) -> Option<OsString> + Send + Sync,
+{
+    #[inline]
+    fn var_os(&self, key: &OsStr) -> Option<OsString> {
+        self(key)
+    }
+}
+
+impl<S> EnvSource for HashMap<OsString, OsString, S>
+where
+    S: BuildHasher + Send + Sync,
+{
+    #[inline]
+    fn var_os(&self, key: &OsStr) -> Option<OsString> {
+        self.get(key).cloned()
+    }
+}
+
+impl EnvSource for BTreeMap<OsString, OsString> {
+    #[inline]
+    fn var_os(&self, key: &OsStr) -> Option<OsString> {
+        self.get(key).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maps() {
+        let pairs = [(OsString::from("EDITOR"), OsString::from("nano"))];
+        let hash_map = HashMap::from(pairs.clone());
+        let btree_map = BTreeMap::from(pairs);
+        for env in [&hash_map as &dyn EnvSource, &btree_map] {
+            assert_eq!(
+                Some(OsString::from("nano")),
+                env.var_os(OsStr::new("EDITOR"))
+            );
+            assert_eq!(None, env.var_os(OsStr::new("VISUAL")));
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let snapshot = [("EDITOR", "nano")].into_iter().collect::<EnvSnapshot>();
+        assert_eq!(
+            Some(OsString::from("nano")),
+            snapshot.var_os(OsStr::new("EDITOR"))
+        );
+        let expected = cfg!(windows).then(|| OsString::from("nano"));
+        assert_eq!(expected, snapshot.var_os(OsStr::new("editor")));
+    }
+
+    #[test]
+    fn test_closure() {
+        let env = |key: &OsStr| (key == "EDITOR").then(|| OsString::from("nano"));
+        assert_eq!(
+            Some(OsString::from("nano")),
+            env.var_os(OsStr::new("EDITOR"))
+        );
+    }
+}
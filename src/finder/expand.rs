@@ -0,0 +1,225 @@
+//! Utilities for expanding `~` and environment variables in an editor's words.
+use super::SplitStyle;
+use crate::ParseError;
+use std::ffi::{OsStr, OsString};
+
+/// The characters that start expansions, and the stand-ins for them when they're quoted
+/// or escaped. The stand-ins are in a private use area, so they're very unlikely to be
+/// in an editor's name.
+const QUOTED: [(char, char); 3] = [
+    ('$', '\u{10FE24}'),
+    ('%', '\u{10FE25}'),
+    ('~', '\u{10FE7E}'),
+];
+
+/// Splits `editor` into words with `style`, and expands each word like [`expand`].
+///
+/// With [`SplitStyle::Posix`], `$`, `%`, and `~` aren't expanded when they're
+/// single-quoted or escaped, like in a shell. A `~` isn't expanded in double quotes
+/// either.
+pub(super) fn split_expand<Env>(
+    editor: &str,
+    style: SplitStyle,
+    mut f: Env,
+) -> Result<Vec<String>, ParseError>
+where
+    Env: FnMut(&OsStr) -> Option<OsString>,
+{
+    let words = match style {
+        SplitStyle::Posix => style.split(&protect(editor))?,
+        SplitStyle::Windows => style.split(editor)?,
+    };
+    let words = words
+        .into_iter()
+        .map(|word| restore(&expand(&word, &mut f)))
+        .collect();
+    Ok(words)
+}
+
+/// Replaces the `$`, `%`, and `~` in `editor` that a POSIX shell wouldn't expand with
+/// stand-ins, before `editor` is split.
+fn protect(editor: &str) -> String {
+    let stand_in = |c: char| {
+        QUOTED
+            .iter()
+            .find(|(quoted, _)| *quoted == c)
+            .map(|(_, stand_in)| *stand_in)
+    };
+    let mut protected = String::with_capacity(editor.len());
+    let mut double_quoted = false;
+    let mut chars = editor.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if !double_quoted => {
+                protected.push(c);
+                for c in chars.by_ref() {
+                    protected.push(stand_in(c).unwrap_or(c));
+                    if c == '\'' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                double_quoted = !double_quoted;
+                protected.push(c);
+            }
+            // NOTE In double quotes, a backslash only escapes some characters, like $.
+            '\\' => match chars.next() {
+                Some(next) if !double_quoted || next == '$' => match stand_in(next) {
+                    Some(stand_in) => protected.push(stand_in),
+                    None => protected.extend([c, next]),
+                },
+                Some(next) => protected.extend([c, next]),
+                None => protected.push(c),
+            },
+            '~' if double_quoted => protected.push(stand_in(c).unwrap_or(c)),
+            c => protected.push(c),
+        }
+    }
+    protected
+}
+
+/// Replaces the stand-ins created by [`protect`] with the characters they stand for.
+fn restore(word: &str) -> String {
+    word.chars()
+        .map(|c| {
+            QUOTED
+                .iter()
+                .find(|(_, stand_in)| *stand_in == c)
+                .map_or(c, |(quoted, _)| *quoted)
+        })
+        .collect()
+}
+
+/// Expands a leading `~` to the home directory, and `$VAR` and `${VAR}` references to
+/// their values. On Windows, `%VAR%` references are expanded too.
+///
+/// References to undefined variables are kept as they are, so that errors mention
+/// them.
+fn expand<Env>(word: &str, f: Env) -> String
+where
+    Env: FnMut(&OsStr) -> Option<OsString>,
+{
+    expand_inner(word, cfg!(windows), f)
+}
+
+/// Expands `word`, optionally expanding `%VAR%` references.
+fn expand_inner<Env>(word: &str, percent: bool, mut f: Env) -> String
+where
+    Env: FnMut(&OsStr) -> Option<OsString>,
+{
+    let mut var =
+        |name: &str| f(OsStr::new(name)).map(|value| value.to_string_lossy().into_owned());
+    let mut expanded = String::with_capacity(word.len());
+    let mut rest = word;
+    if let Some(after) = rest.strip_prefix('~') {
+        if after.is_empty() || after.starts_with(std::path::is_separator) {
+            let home = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+            if let Some(home) = var(home) {
+                expanded.push_str(&home);
+                rest = after;
+            }
+        }
+    }
+    while let Some(index) = rest.find(['$', '%']) {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index..];
+        let reference = match rest.as_bytes()[0] {
+            b'$' => dollar_reference(rest),
+            _ if percent => percent_reference(rest),
+            _ => None,
+        };
+        let Some((name, len)) = reference else {
+            expanded.push_str(&rest[..1]);
+            rest = &rest[1..];
+            continue;
+        };
+        match var(name) {
+            Some(value) => expanded.push_str(&value),
+            None => expanded.push_str(&rest[..len]),
+        }
+        rest = &rest[len..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Parses a `$VAR` or `${VAR}` reference at the start of `s`. Returns the variable's
+/// name and the length of the reference.
+fn dollar_reference(s: &str) -> Option<(&str, usize)> {
+    let s = &s[1..];
+    if let Some(braced) = s.strip_prefix('{') {
+        let end = braced.find('}')?;
+        let name = &braced[..end];
+        return is_name(name).then_some((name, end + 3));
+    }
+    let end = s
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(s.len());
+    let name = &s[..end];
+    is_name(name).then_some((name, end + 1))
+}
+
+/// Parses a `%VAR%` reference at the start of `s`. Returns the variable's name and
+/// the length of the reference.
+fn percent_reference(s: &str) -> Option<(&str, usize)> {
+    let s = &s[1..];
+    let end = s.find('%')?;
+    let name = &s[..end];
+    (!name.is_empty()).then_some((name, end + 2))
+}
+
+/// Checks if `name` is a valid variable name for a `$VAR` reference.
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn env(key: &OsStr) -> Option<OsString> {
+        match key.to_str() {
+            Some("HOME" | "USERPROFILE") => Some(OsString::from("/home/me")),
+            Some("TOOLS") => Some(OsString::from("/opt/tools")),
+            _ => None,
+        }
+    }
+
+    #[rstest]
+    #[case::plain("vim", false, "vim")]
+    #[case::tilde("~/bin/myedit", false, "/home/me/bin/myedit")]
+    #[case::tilde_only("~", false, "/home/me")]
+    #[case::tilde_user("~other/bin", false, "~other/bin")]
+    #[case::dollar("$TOOLS/edit", false, "/opt/tools/edit")]
+    #[case::braced("${TOOLS}edit", false, "/opt/toolsedit")]
+    #[case::undefined("$UNDEFINED/edit", false, "$UNDEFINED/edit")]
+    #[case::lone_dollar("cost$", false, "cost$")]
+    #[case::percent_ignored("%TOOLS%/edit", false, "%TOOLS%/edit")]
+    #[case::percent("%TOOLS%/edit", true, "/opt/tools/edit")]
+    #[case::percent_unclosed("100%", true, "100%")]
+    fn test_expand(#[case] word: &str, #[case] percent: bool, #[case] expected: &str) {
+        assert_eq!(expected, expand_inner(word, percent, env));
+    }
+
+    #[rstest]
+    #[case::unquoted("edit $TOOLS ~", &["edit", "/opt/tools", "/home/me"])]
+    #[case::single_quoted("edit '$TOOLS' '~'", &["edit", "$TOOLS", "~"])]
+    #[case::partly_quoted("'$TOOLS'/$TOOLS", &["$TOOLS//opt/tools"])]
+    #[case::double_quoted(r#"edit "$TOOLS" "~""#, &["edit", "/opt/tools", "~"])]
+    #[case::escaped(r#"edit \$TOOLS \~ "\$TOOLS""#, &["edit", "$TOOLS", "~", "$TOOLS"])]
+    #[case::escaped_other(r#"edit\ it "a\b""#, &["edit it", r"a\b"])]
+    #[case::quote_in_double(r#""it's $TOOLS""#, &["it's /opt/tools"])]
+    fn test_split_expand_posix(#[case] editor: &str, #[case] expected: &[&str]) {
+        let actual = split_expand(editor, SplitStyle::Posix, env).unwrap();
+        assert_eq!(expected, actual.as_slice());
+    }
+
+    #[test]
+    fn test_split_expand_single_quoted_percent() {
+        let actual = split_expand("edit '%TOOLS%'", SplitStyle::Posix, env).unwrap();
+        assert_eq!(["edit", "%TOOLS%"], actual.as_slice());
+    }
+}
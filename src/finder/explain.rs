@@ -0,0 +1,399 @@
+//! Contains the [`Explanation`] struct, which traces how an editor was found.
+use super::context::Context;
+use super::{EditorSource, Finder, ResolvedEditor, is_blank};
+use std::ffi::{OsStr, OsString};
+use std::fmt::{self, Display};
+#[cfg(feature = "which")]
+use std::path::PathBuf;
+
+/// A step-by-step trace of how a [`Finder`] found an editor.
+///
+/// Create one with [`Finder::explain`]. Its [`Display`] implementation describes each
+/// step on its own line, and, with the `serde` feature, it can be serialized for
+/// machine-readable output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Explanation {
+    /// The steps, in the order they happened.
+    steps: Vec<Step>,
+    /// The editor that was found.
+    resolved: ResolvedEditor,
+}
+
+/// A single step of an [`Explanation`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Step {
+    /// An environment variable was read.
+    Variable {
+        /// The variable's name.
+        #[cfg_attr(feature = "serde", serde(with = "super::os_serde::os_string"))]
+        name: OsString,
+        /// The variable's value, or `None` if it isn't set.
+        #[cfg_attr(feature = "serde", serde(with = "super::os_serde::option"))]
+        value: Option<OsString>,
+    },
+    /// An editor's command was searched for on `$PATH`.
+    #[cfg(feature = "which")]
+    SearchPath {
+        /// The command that was searched for.
+        #[cfg_attr(feature = "serde", serde(with = "super::os_serde::os_string"))]
+        command: OsString,
+        /// The directories on `$PATH`, in the order they're searched.
+        directories: Vec<PathBuf>,
+        /// The path to the command, or `None` if it wasn't found.
+        found: Option<PathBuf>,
+    },
+    /// An editor was found, and either chosen or skipped.
+    Candidate {
+        /// The editor, and where it was found.
+        editor: ResolvedEditor,
+        /// Whether the editor was chosen, or why it was skipped.
+        outcome: Outcome,
+    },
+}
+
+/// Whether an editor found by a [`Finder`] was chosen, or why it was skipped.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Outcome {
+    /// The editor was chosen.
+    Chosen,
+    /// The editor was skipped, because it's empty or only whitespace.
+    Blank,
+    /// The editor was skipped, because its command couldn't be found on `$PATH`. See
+    /// [`FinderBuilder::skip_missing_editors`](super::FinderBuilder::skip_missing_editors).
+    #[cfg(feature = "which")]
+    NotOnPath,
+    /// The editor was skipped, because another editor suits the current context
+    /// better. See
+    /// [`FinderBuilder::prefer_context_appropriate`](super::FinderBuilder::prefer_context_appropriate).
+    NotPreferred,
+}
+
+impl Finder {
+    /// Finds an editor like [`Finder::resolve`], and traces each step.
+    ///
+    /// The trace includes the environment variables that were checked and their values,
+    /// each editor that was found and why it was skipped, and the directories that were
+    /// searched for an editor's command. This can help debug why the wrong editor is
+    /// opened.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let explanation = Finder::new().explain();
+    /// println!("{explanation}");
+    /// ```
+    pub fn explain(&self) -> Explanation {
+        let mut steps = Vec::new();
+        let context = self
+            .prefer_context
+            .then(|| Context::detect(|key| self.read_var(&mut steps, key)));
+        // NOTE The indices of the steps for editors that could be chosen.
+        let mut usable = Vec::new();
+        'sources: for source in &self.sources {
+            let editors = self
+                .source_editors(*source, &mut |key| self.read_var(&mut steps, key))
+                .collect::<Vec<_>>();
+            for editor in editors {
+                let outcome = self.candidate_outcome(&mut steps, &editor);
+                if outcome == Outcome::Chosen {
+                    usable.push(steps.len());
+                }
+                steps.push(Step::Candidate { editor, outcome });
+                // NOTE Without a context, the first usable editor is always chosen.
+                if context.is_none() && !usable.is_empty() {
+                    break 'sources;
+                }
+            }
+        }
+
+        let mut editors = usable.iter().filter_map(|index| match &steps[*index] {
+            Step::Candidate { editor, .. } => Some(editor.clone()),
+            _ => None,
+        });
+        let resolved = match context {
+            Some(context) => context.pick(editors),
+            None => editors.next(),
+        };
+        let resolved = match resolved {
+            Some(resolved) => {
+                let mut chosen = false;
+                for index in usable {
+                    if let Step::Candidate { editor, outcome } = &mut steps[index] {
+                        if !chosen && *editor == resolved {
+                            chosen = true;
+                        } else {
+                            *outcome = Outcome::NotPreferred;
+                        }
+                    }
+                }
+                resolved
+            }
+            None => {
+                let resolved = self.explain_fallback(&mut steps);
+                steps.push(Step::Candidate {
+                    editor: resolved.clone(),
+                    outcome: Outcome::Chosen,
+                });
+                resolved
+            }
+        };
+        Explanation { steps, resolved }
+    }
+
+    /// Reads an environment variable, and records it as a step.
+    fn read_var(&self, steps: &mut Vec<Step>, key: &OsStr) -> Option<OsString> {
+        let value = self.var_os(key);
+        steps.push(Step::Variable {
+            name: key.to_owned(),
+            value: value.clone(),
+        });
+        value
+    }
+
+    /// Checks if a found editor can be chosen, like [`Finder::is_candidate`], and
+    /// records any search of `$PATH` as a step.
+    fn candidate_outcome(&self, steps: &mut Vec<Step>, editor: &ResolvedEditor) -> Outcome {
+        if is_blank(editor.name()) {
+            return Outcome::Blank;
+        }
+        #[cfg(feature = "which")]
+        if self.skip_missing && !self.search_path(steps, editor.name()) {
+            return Outcome::NotOnPath;
+        }
+        #[cfg(not(feature = "which"))]
+        let _ = steps;
+        Outcome::Chosen
+    }
+
+    /// Picks the fallback editor, like [`Finder::fallback_editor`], and records any
+    /// search of `$PATH` as a step.
+    fn explain_fallback(&self, steps: &mut Vec<Step>) -> ResolvedEditor {
+        if let Some(editor) = self.vscode_fallback() {
+            return ResolvedEditor::new(editor, EditorSource::Fallback);
+        }
+        #[cfg(feature = "which")]
+        if let Some(fallback) = self
+            .fallbacks
+            .iter()
+            .find(|fallback| self.search_path(steps, fallback))
+        {
+            return ResolvedEditor::new(self.fallback_name(fallback), EditorSource::Fallback);
+        }
+        #[cfg(not(feature = "which"))]
+        let _ = steps;
+        ResolvedEditor::new(self.fallback_editor(), EditorSource::Fallback)
+    }
+
+    /// Searches for an editor's command on `$PATH` like [`Finder::is_on_path`], and
+    /// records the search as a step.
+    #[cfg(feature = "which")]
+    fn search_path(&self, steps: &mut Vec<Step>, editor: &OsStr) -> bool {
+        let Some(command) = self.editor_command(editor) else {
+            return false;
+        };
+        let directories = self
+            .path_var()
+            .map(|paths| std::env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        let found = self.which(&command).ok();
+        let is_found = found.is_some();
+        steps.push(Step::SearchPath {
+            command,
+            directories,
+            found,
+        });
+        is_found
+    }
+}
+
+impl Explanation {
+    /// The steps, in the order they happened.
+    #[inline]
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// The editor that was found.
+    #[inline]
+    pub fn resolved(&self) -> &ResolvedEditor {
+        &self.resolved
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{step}")?;
+        }
+        let name = self.resolved.name().to_string_lossy();
+        write!(f, "Using {name} from {}", self.resolved.source())
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Variable { name, value: None } => {
+                write!(f, "${} is not set", name.to_string_lossy())
+            }
+            Self::Variable {
+                name,
+                value: Some(value),
+            } => write!(f, "${} is {value:?}", name.to_string_lossy()),
+            #[cfg(feature = "which")]
+            Self::SearchPath {
+                command,
+                directories,
+                found,
+            } => {
+                let command = command.to_string_lossy();
+                write!(
+                    f,
+                    "Searched for {command} in {} directories on $PATH: ",
+                    directories.len()
+                )?;
+                match found {
+                    Some(path) => write!(f, "found {}", path.display()),
+                    None => write!(f, "not found"),
+                }
+            }
+            Self::Candidate { editor, outcome } => {
+                let name = editor.name().to_string_lossy();
+                write!(f, "{name} from {}: {outcome}", editor.source())
+            }
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Chosen => write!(f, "chosen"),
+            Self::Blank => write!(f, "skipped because it's blank"),
+            #[cfg(feature = "which")]
+            Self::NotOnPath => write!(f, "skipped because it isn't on $PATH"),
+            Self::NotPreferred => {
+                write!(f, "skipped because another editor suits the context better")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnvSnapshot;
+
+    fn variable(name: &str, value: Option<&str>) -> Step {
+        Step::Variable {
+            name: name.into(),
+            value: value.map(OsString::from),
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let env = [("VISUAL", " "), ("EDITOR", "nano")]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::builder()
+            .extra_environment_variables(["MY_TOOL_EDITOR"])
+            .env_source(env)
+            .build();
+        let explanation = finder.explain();
+        let expected = [
+            variable("MY_TOOL_EDITOR", None),
+            variable("VISUAL", Some(" ")),
+            variable("EDITOR", Some("nano")),
+            Step::Candidate {
+                editor: ResolvedEditor::new(" ", EditorSource::StandardEnv("VISUAL".into())),
+                outcome: Outcome::Blank,
+            },
+            Step::Candidate {
+                editor: ResolvedEditor::new("nano", EditorSource::StandardEnv("EDITOR".into())),
+                outcome: Outcome::Chosen,
+            },
+        ];
+        assert_eq!(expected, explanation.steps());
+        assert_eq!(&finder.resolve(), explanation.resolved());
+        assert!(
+            explanation
+                .to_string()
+                .ends_with("nano from $EDITOR: chosen\nUsing nano from $EDITOR")
+        );
+    }
+
+    #[test]
+    fn test_explain_fallback() {
+        let finder = Finder::builder()
+            .fallback("fallback-editor-that-does-not-exist")
+            .env_source(EnvSnapshot::default())
+            .build();
+        let explanation = finder.explain();
+        assert_eq!(&finder.resolve(), explanation.resolved());
+        assert_eq!(
+            Some(&Step::Candidate {
+                editor: finder.resolve(),
+                outcome: Outcome::Chosen,
+            }),
+            explanation.steps().last()
+        );
+    }
+
+    #[cfg(all(unix, feature = "which"))]
+    #[test]
+    fn test_explain_fallback_expanded() {
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join("edit");
+        crate::finder::test_utils::create_executable(&editor);
+        let env = [("TOOLS", dir.path().as_os_str())]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#""$TOOLS/edit""#)
+            .split_style(crate::SplitStyle::Windows)
+            .env_source(env)
+            .build();
+        let explanation = finder.explain();
+        assert_eq!(editor.as_os_str(), finder.resolve().name());
+        assert_eq!(&finder.resolve(), explanation.resolved());
+    }
+
+    #[cfg(feature = "which")]
+    #[test]
+    fn test_explain_not_on_path() {
+        let env = [("EDITOR", "editor-that-does-not-exist"), ("PATH", "")]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::builder()
+            .skip_missing_editors(true)
+            .env_source(env)
+            .build();
+        let explanation = finder.explain();
+        let outcome = explanation.steps().iter().find_map(|step| match step {
+            Step::Candidate { editor, outcome }
+                if editor.name() == "editor-that-does-not-exist" =>
+            {
+                Some(*outcome)
+            }
+            _ => None,
+        });
+        assert_eq!(Some(Outcome::NotOnPath), outcome);
+        assert!(
+            explanation
+                .steps()
+                .iter()
+                .any(|step| matches!(step, Step::SearchPath { found: None, .. }))
+        );
+    }
+}
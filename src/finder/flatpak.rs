@@ -0,0 +1,74 @@
+//! Utilities for opening editors on the host from a Flatpak sandbox.
+use std::ffi::OsString;
+use std::path::Path;
+
+/// The file that Flatpak creates in the root of every sandbox.
+const FLATPAK_INFO: &str = "/.flatpak-info";
+
+/// Checks if this is running in a Flatpak sandbox.
+pub(super) fn is_sandboxed() -> bool {
+    cfg!(target_os = "linux") && Path::new(FLATPAK_INFO).exists()
+}
+
+/// Gets the words that run a command on the host with `flatpak-spawn`. The command is
+/// passed after these words.
+///
+/// The host command doesn't inherit the sandbox's environment or working directory, so
+/// `envs` and `dir` are passed to `flatpak-spawn`. Variables without a value are unset.
+pub(super) fn host_invocation(
+    envs: &[(OsString, Option<OsString>)],
+    dir: Option<&Path>,
+) -> Vec<OsString> {
+    let mut words = vec![OsString::from("flatpak-spawn"), OsString::from("--host")];
+    words.extend(envs.iter().map(|(key, value)| match value {
+        Some(value) => {
+            let mut arg = OsString::from("--env=");
+            arg.push(key);
+            arg.push("=");
+            arg.push(value);
+            arg
+        }
+        None => {
+            let mut arg = OsString::from("--unset-env=");
+            arg.push(key);
+            arg
+        }
+    }));
+    if let Some(dir) = dir {
+        let mut arg = OsString::from("--directory=");
+        arg.push(dir);
+        words.push(arg);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_invocation() {
+        let envs = [
+            (
+                OsString::from("GIT_DIR"),
+                Some(OsString::from("/repo/.git")),
+            ),
+            (OsString::from("LD_PRELOAD"), None),
+        ];
+        let actual = host_invocation(&envs, Some(Path::new("/repo")));
+        assert_eq!(
+            [
+                "flatpak-spawn",
+                "--host",
+                "--env=GIT_DIR=/repo/.git",
+                "--unset-env=LD_PRELOAD",
+                "--directory=/repo"
+            ],
+            actual.as_slice()
+        );
+        assert_eq!(
+            ["flatpak-spawn", "--host"],
+            host_invocation(&[], None).as_slice()
+        );
+    }
+}
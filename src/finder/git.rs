@@ -0,0 +1,59 @@
+//! Utilities for finding git's editor.
+use super::{EditorSource, Finder, ResolvedEditor};
+use std::ffi::{OsStr, OsString};
+use std::process::{Command, Stdio};
+
+impl Finder {
+    /// The environment variable git uses to define its editor.
+    const GIT_EDITOR_ENV_VAR: &'static str = "GIT_EDITOR";
+
+    /// Finds git's editor from `$GIT_EDITOR` or `git config core.editor`.
+    pub(super) fn find_git_editor<Env>(&self, mut f: Env) -> Option<ResolvedEditor>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        f(OsStr::new(Self::GIT_EDITOR_ENV_VAR))
+            .map(|name| ResolvedEditor::new(name, EditorSource::GitEnv))
+            .or_else(|| {
+                git_config_core_editor()
+                    .map(|name| ResolvedEditor::new(name, EditorSource::GitConfig))
+            })
+    }
+}
+
+/// Gets the value of `core.editor` from `git config`.
+///
+/// Returns `None` if git isn't installed or the option isn't set.
+fn git_config_core_editor() -> Option<OsString> {
+    let output = Command::new("git")
+        .args(["config", "core.editor"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut stdout = output.stdout;
+    while stdout.last().is_some_and(u8::is_ascii_whitespace) {
+        stdout.pop();
+    }
+    if stdout.is_empty() {
+        return None;
+    }
+    bytes_to_os_string(stdout)
+}
+
+/// Converts git's output into an [`OsString`].
+#[cfg(unix)]
+fn bytes_to_os_string(bytes: Vec<u8>) -> Option<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+
+    Some(OsString::from_vec(bytes))
+}
+
+/// Converts git's output into an [`OsString`].
+#[cfg(not(unix))]
+fn bytes_to_os_string(bytes: Vec<u8>) -> Option<OsString> {
+    String::from_utf8(bytes).ok().map(OsString::from)
+}
@@ -0,0 +1,124 @@
+//! Utilities for opening files in the editor that's hosting the current terminal, like
+//! Neovim's `:terminal`.
+use super::{Finder, emacs};
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// Finds the editor hosting the terminal that this is running in, and the arguments that
+/// send a file to it.
+///
+/// - In Neovim, `$NVIM` is the server's address. Neovim can't wait for a remote file,
+///   so `nvr --remote-wait` is used when waiting.
+/// - In Vim, `$VIM_TERMINAL` is set, and `$VIM_SERVERNAME` is the server's name if Vim
+///   has a server.
+/// - In Emacs, `$INSIDE_EMACS` is set, and an Emacs server must be running.
+///
+/// Returns `None` if this isn't running in an editor, or if the file can't be sent to
+/// it.
+pub(super) fn find(finder: &Finder, wait: bool) -> Option<(PathBuf, Vec<OsString>)> {
+    let var = |key: &str| {
+        finder
+            .var_os(OsStr::new(key))
+            .filter(|value| !value.is_empty())
+    };
+    if let Some(server) = var("NVIM") {
+        let (command, args) = if wait {
+            ("nvr", ["--servername", "--remote-wait"])
+        } else {
+            ("nvim", ["--server", "--remote"])
+        };
+        let [server_flag, remote_flag] = args.map(OsString::from);
+        let args = vec![server_flag, server, remote_flag];
+        return Some((find_command(finder, command)?, args));
+    }
+    if let Some(server) = var("VIM_SERVERNAME").filter(|_| var("VIM_TERMINAL").is_some()) {
+        let remote_flag = if wait { "--remote-wait" } else { "--remote" };
+        let args = vec![
+            OsString::from("--servername"),
+            server,
+            OsString::from(remote_flag),
+        ];
+        return Some((find_command(finder, "vim")?, args));
+    }
+    if var("INSIDE_EMACS").is_some() && emacs::is_server_running(finder) {
+        let emacsclient = emacs::emacsclient(finder, Path::new("emacs"));
+        let args = if wait {
+            Vec::new()
+        } else {
+            vec![OsString::from("-n")]
+        };
+        return Some((emacsclient, args));
+    }
+    None
+}
+
+/// Finds `command` on `$PATH`. Without the `which` feature, the command is assumed to
+/// be installed.
+fn find_command(finder: &Finder, command: &str) -> Option<PathBuf> {
+    #[cfg(feature = "which")]
+    return finder.which(OsStr::new(command)).ok();
+    #[cfg(not(feature = "which"))]
+    {
+        let _ = finder;
+        Some(PathBuf::from(command))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnvSnapshot;
+    #[cfg(unix)]
+    use crate::finder::test_utils::create_executable;
+    #[cfg(unix)]
+    use rstest::rstest;
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::nvim(&[("NVIM", "/run/nvim.sock")], false, Some(("nvim", &["--server", "/run/nvim.sock", "--remote"][..])))]
+    #[case::nvim_wait(&[("NVIM", "/run/nvim.sock")], true, Some(("nvr", &["--servername", "/run/nvim.sock", "--remote-wait"][..])))]
+    #[case::vim(&[("VIM_TERMINAL", "900"), ("VIM_SERVERNAME", "VIM")], true, Some(("vim", &["--servername", "VIM", "--remote-wait"][..])))]
+    #[case::vim_without_server(&[("VIM_TERMINAL", "900")], true, None)]
+    #[case::none(&[], true, None)]
+    fn test_find(
+        #[case] env: &[(&str, &str)],
+        #[case] wait: bool,
+        #[case] expected: Option<(&str, &[&str])>,
+    ) {
+        let bin = tempfile::tempdir().unwrap();
+        for command in ["nvim", "nvr", "vim"] {
+            let path = bin.path().join(command);
+            create_executable(&path);
+        }
+        let path = bin.path().as_os_str().to_owned();
+        let env = env
+            .iter()
+            .map(|(key, value)| (*key, OsString::from(value)))
+            .chain([("PATH", path)])
+            .collect::<EnvSnapshot>();
+        let actual = find(&Finder::with_env_source(env), wait);
+        let actual = actual.as_ref().map(|(command, args)| {
+            let args = args
+                .iter()
+                .map(|arg| arg.to_str().unwrap())
+                .collect::<Vec<_>>();
+            (command.file_name().unwrap().to_str().unwrap(), args)
+        });
+        let expected = expected.map(|(command, args)| (command, args.to_vec()));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_find_emacs_without_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().as_os_str();
+        let env = [
+            ("INSIDE_EMACS", OsStr::new("29.1,comint")),
+            ("XDG_RUNTIME_DIR", dir),
+            ("TMPDIR", dir),
+        ]
+        .into_iter()
+        .collect::<EnvSnapshot>();
+        assert_eq!(None, find(&Finder::with_env_source(env), true));
+    }
+}
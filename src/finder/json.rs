@@ -0,0 +1,92 @@
+//! Describes the resolved editor as JSON for other tools.
+use super::{EditorSource, Finder};
+use crate::Error;
+use serde_json::{Value, json};
+
+impl Finder {
+    /// The version of the schema used by [`Finder::resolve_json`]. This changes when
+    /// fields are removed or change meaning, but not when fields are added.
+    pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+    /// Finds the editor, and describes it as JSON for other tools and dashboards.
+    ///
+    /// The JSON is an object with these fields:
+    ///
+    /// - `schema_version` - [`Finder::JSON_SCHEMA_VERSION`].
+    /// - `editor` - The editor, like `code --wait`.
+    /// - `command` - The editor's command, like `code`.
+    /// - `args` - The arguments that are passed to the command, like `["--wait"]`.
+    /// - `path` - The absolute path to the command, or `null` if it can't be found.
+    /// - `source` - Where the editor was found, like `$EDITOR`.
+    /// - `fallback` - `true` if no editor was configured, so the fallback was used.
+    ///
+    /// Values that aren't valid unicode are converted lossily.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let json = Finder::new().resolve_json().expect("Should split the editor");
+    /// println!("{json}");
+    /// ```
+    pub fn resolve_json(&self) -> Result<Value, Error> {
+        let resolved = self.resolve();
+        let (command, args) = self.split_editor(resolved.name(), |key| self.var_os(key))?;
+        let path = self
+            .find_command(&command)
+            .ok()
+            .and_then(|path| std::path::absolute(path).ok());
+        let args = args
+            .iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>();
+        Ok(json!({
+            "schema_version": Self::JSON_SCHEMA_VERSION,
+            "editor": resolved.name().to_string_lossy(),
+            "command": command.to_string_lossy(),
+            "args": args,
+            "path": path.map(|path| path.to_string_lossy().into_owned()),
+            "source": resolved.source().to_string(),
+            "fallback": *resolved.source() == EditorSource::Fallback,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnvSnapshot;
+
+    #[test]
+    fn test_resolve_json() {
+        let env = [
+            ("EDITOR", "editor-that-does-not-exist --wait"),
+            ("PATH", ""),
+        ]
+        .into_iter()
+        .collect::<EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let expected = json!({
+            "schema_version": 1,
+            "editor": "editor-that-does-not-exist --wait",
+            "command": "editor-that-does-not-exist",
+            "args": ["--wait"],
+            "path": null,
+            "source": "$EDITOR",
+            "fallback": false,
+        });
+        assert_eq!(expected, finder.resolve_json().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_json_fallback() {
+        let finder = Finder::builder()
+            .fallback("fallback-editor-that-does-not-exist")
+            .env_source(EnvSnapshot::default())
+            .build();
+        let json = finder.resolve_json().unwrap();
+        assert_eq!(json!(true), json["fallback"]);
+        assert_eq!(json!("the fallback editor"), json["source"]);
+    }
+}
@@ -0,0 +1,119 @@
+//! Utilities for finding commands with the user's login shell.
+use super::is_executable;
+use std::ffi::OsStr;
+use std::io::Read;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long the shell has to find a command before it's killed, so that slow or
+/// blocking init files can't hang finding an editor.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Finds `command` by running `shell` as an interactive login shell, so that its init
+/// files can add the command, like version managers do. Returns `None` if the shell
+/// doesn't find an executable, or if it takes longer than [`TIMEOUT`].
+pub(super) fn find(shell: &OsStr, command: &OsStr) -> Option<PathBuf> {
+    find_with_timeout(shell, command, TIMEOUT)
+}
+
+/// Finds `command` with `shell`, killing the shell if it takes longer than `timeout`.
+fn find_with_timeout(shell: &OsStr, command: &OsStr, timeout: Duration) -> Option<PathBuf> {
+    /// How often the shell is checked for exiting.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    let deadline = Instant::now() + timeout;
+    // NOTE The command is passed as an argument, so that it isn't interpreted by the
+    //      shell.
+    let mut child = Command::new(shell)
+        .args(["-l", "-i", "-c", r#"command -v "$1""#, "sh"])
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let mut stdout = child.stdout.take()?;
+    // NOTE The output is read from another thread, so that the shell can be killed if
+    //      it doesn't close its output in time.
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = stdout.read_to_end(&mut output);
+        let _ = sender.send(output);
+    });
+    let output = receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()));
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) if output.is_ok() => break status,
+            Ok(None) if Instant::now() < deadline && output.is_ok() => {
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+    if !status.success() {
+        return None;
+    }
+    parse_output(&output.ok()?)
+}
+
+/// Gets the path from the output of `command -v`. Init files can print other lines
+/// first, so the last line is used. Aliases and functions aren't paths, so they're
+/// ignored.
+fn parse_output(stdout: &[u8]) -> Option<PathBuf> {
+    let line = stdout
+        .split(|byte| *byte == b'\n')
+        .map(<[u8]>::trim_ascii)
+        .rfind(|line| !line.is_empty())?;
+    let path = PathBuf::from(OsStr::from_bytes(line));
+    (path.is_absolute() && is_executable(&path)).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finder::test_utils::create_script;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::path(b"/bin/sh\n", Some("/bin/sh"))]
+    #[case::init_output(b"Welcome!\n/bin/sh\n\n", Some("/bin/sh"))]
+    #[case::alias(b"alias vim='nvim'\n", None)]
+    #[case::builtin(b"echo\n", None)]
+    #[case::missing(b"/does/not/exist\n", None)]
+    #[case::empty(b"", None)]
+    fn test_parse_output(#[case] stdout: &[u8], #[case] expected: Option<&str>) {
+        assert_eq!(expected.map(PathBuf::from), parse_output(stdout));
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(
+            Some(PathBuf::from("/bin/sh")),
+            find(OsStr::new("sh"), OsStr::new("/bin/sh"))
+        );
+        assert_eq!(
+            None,
+            find(OsStr::new("sh"), OsStr::new("command-that-does-not-exist"))
+        );
+    }
+
+    #[test]
+    fn test_find_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let shell = dir.path().join("slow-shell");
+        create_script(&shell, "#!/bin/sh\nexec sleep 10\n");
+        let start = Instant::now();
+        let timeout = Duration::from_millis(100);
+        let actual = find_with_timeout(shell.as_os_str(), OsStr::new("sh"), timeout);
+        assert_eq!(None, actual);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
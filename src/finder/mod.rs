@@ -1,22 +1,173 @@
 //! Contains the [`Finder`] struct which helps find editors.
 
-#[cfg(any(feature = "open", feature = "split", feature = "which"))]
 use crate::Error;
-use std::env;
+use context::Context;
+use std::borrow::Cow;
 use std::ffi::{OsStr, OsString};
+use std::fmt;
 #[cfg(feature = "which")]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+#[cfg(feature = "which")]
+use std::sync::{Mutex, PoisonError};
+
+pub use builder::FinderBuilder;
+#[cfg(feature = "tempedit")]
+pub use edit_builder::EditBuilder;
+#[cfg(feature = "open")]
+pub use edit_each::EditedFile;
+pub use env_source::{EnvSnapshot, EnvSource, ProcessEnv};
+pub use explain::{Explanation, Outcome, Step};
+#[cfg(feature = "open")]
+pub use open_options::{MacApp, OpenFallback, OpenOptions, ProcessGroup, TerminalMode};
+#[cfg(feature = "which")]
+pub use probe::InstalledEditor;
+pub use resolved::{EditorSource, ResolvedEditor};
+pub use resolver::Resolver;
+#[cfg(feature = "open")]
+pub use session::EditorSession;
+pub use source::Source;
+#[cfg(feature = "split")]
+pub use split::SplitStyle;
+
+mod builder;
+mod context;
+mod debian;
+#[cfg(feature = "tempedit")]
+mod edit;
+#[cfg(feature = "tempedit")]
+mod edit_builder;
+#[cfg(feature = "open")]
+mod edit_each;
+#[cfg(feature = "open")]
+mod emacs;
+mod env_source;
+#[cfg(feature = "split")]
+mod expand;
+mod explain;
+#[cfg(feature = "open")]
+mod flatpak;
+#[cfg(feature = "git")]
+mod git;
+#[cfg(feature = "open")]
+mod host_editor;
+#[cfg(all(feature = "json", feature = "which"))]
+mod json;
+#[cfg(all(unix, feature = "which"))]
+mod login_shell;
+#[cfg(all(windows, feature = "open"))]
+mod msys;
 #[cfg(feature = "open")]
 mod open;
+#[cfg(feature = "open")]
+mod open_options;
+#[cfg(feature = "serde")]
+mod os_serde;
+#[cfg(any(feature = "which", feature = "open"))]
+mod packaged;
+#[cfg(feature = "which")]
+mod probe;
+#[cfg(feature = "prompt")]
+mod prompt;
+#[cfg(feature = "windows-registry")]
+mod registry;
+mod resolved;
+mod resolver;
+#[cfg(feature = "open")]
+mod session;
+mod source;
+#[cfg(feature = "split")]
+mod split;
+#[cfg(all(unix, feature = "tempedit"))]
+mod sudo_edit;
+#[cfg(feature = "which")]
+mod suggest;
+#[cfg(feature = "open")]
+mod template;
+#[cfg(feature = "open")]
+mod terminal;
+#[cfg(all(test, unix))]
+mod test_utils;
+#[cfg(feature = "open")]
+mod wait;
+#[cfg(all(windows, feature = "which"))]
+mod windows_apps;
+#[cfg(all(target_os = "linux", feature = "open"))]
+mod wsl;
+
+/// An editor command's path and its arguments.
+#[cfg(feature = "which")]
+type EditorCommand = (PathBuf, Vec<OsString>);
 
 /// Helper to find and open an editor.
 ///
 /// Can take extra environment variable keys to define environment variables specific
-/// to your tool that should be looked up first.
-#[derive(Default)]
+/// to your tool that should be looked up first. Values that are empty or only
+/// whitespace (e.g. `EDITOR=""`) are treated as unset.
+///
+/// Use [`Finder::builder`] to configure more than one option at a time.
+///
+/// Two finders are equal if they're configured the same way. [`EnvSource`]s and
+/// [`Resolver`]s can't be compared, so they're only equal if they're shared by cloning a
+/// finder.
+///
+/// With the `serde` feature, a finder's configuration can be serialized, like in your
+/// application's config. Its [`EnvSource`], [`Resolver`]s, and cache aren't serialized,
+/// and missing options are deserialized as their defaults.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Finder {
+    /// Where environment variables are read from, or `None` for the process's
+    /// environment.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    env: Option<Arc<dyn EnvSource>>,
     /// Extra environment variables to search for.
+    #[cfg_attr(feature = "serde", serde(with = "os_serde::vec"))]
     extra_env_vars: Vec<OsString>,
+    /// The editor from the application's config.
+    #[cfg_attr(feature = "serde", serde(with = "os_serde::option"))]
+    config_value: Option<OsString>,
+    /// Custom resolvers to search with, in order.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    resolvers: Vec<Arc<dyn Resolver>>,
+    /// Standard environment variables to search for, in order.
+    #[cfg_attr(feature = "serde", serde(with = "os_serde::vec"))]
+    standard_env_vars: Vec<OsString>,
+    /// The editors to use when no environment variable is defined, in order of
+    /// preference.
+    #[cfg_attr(feature = "serde", serde(with = "os_serde::vec"))]
+    fallbacks: Vec<OsString>,
+    /// The sources to search, in order.
+    sources: Vec<Source>,
+    /// If editors that suit the current context are preferred.
+    prefer_context: bool,
+    /// If `$VISUAL` is only used on a capable terminal.
+    posix_visual: bool,
+    /// If VS Code is fallen back to in VS Code's integrated terminal.
+    vscode_terminal: bool,
+    /// The rules used to split an editor into words.
+    #[cfg(feature = "split")]
+    split_style: SplitStyle,
+    /// If editors that can't be found on `$PATH` are skipped.
+    #[cfg(feature = "which")]
+    skip_missing: bool,
+    /// If an editor's command can be a relative path, like `./bin/edit`.
+    #[cfg(feature = "which")]
+    allow_relative_paths: bool,
+    /// If commands that can't be found on `$PATH` are found with the user's login
+    /// shell.
+    #[cfg(feature = "which")]
+    login_shell: bool,
+    /// The `$PATH` to find commands on instead of the environment's.
+    #[cfg(feature = "which")]
+    #[cfg_attr(feature = "serde", serde(with = "os_serde::option"))]
+    search_path: Option<OsString>,
+    /// The editor's command and its path, remembered after they are first found, if
+    /// caching is enabled.
+    #[cfg(feature = "which")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cache: Option<Arc<Mutex<Option<EditorCommand>>>>,
 }
 
 impl Finder {
@@ -27,12 +178,57 @@ impl Finder {
     /// Basic environment variables to look up.
     const STANDARD_ENV_VARS: [&'static str; 2] = ["VISUAL", "EDITOR"];
 
+    /// The editors that are fallen back to by default, in order of preference.
+    ///
+    /// This is `notepad.exe` on Windows, and `vi` on every other platform. With the
+    /// `which` feature on Windows, Notepad++ and VS Code are tried before Notepad, from
+    /// their standard install locations, and the one that's used is expanded to its
+    /// path, like `C:\Program Files\Notepad++\notepad++.exe`. Extend this with
+    /// [`FinderBuilder::fallbacks`] to try other editors too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let fallbacks = ["hx"].iter().chain(Finder::DEFAULT_FALLBACKS);
+    /// let finder = Finder::builder().fallbacks(fallbacks).build();
+    /// ```
+    pub const DEFAULT_FALLBACKS: &'static [&'static str] = if cfg!(all(windows, feature = "which"))
+    {
+        // NOTE These are expanded and searched for, so only the installed ones are used.
+        &[
+            r#""%ProgramFiles%\Notepad++\notepad++.exe""#,
+            r#""%LOCALAPPDATA%\Programs\Microsoft VS Code\bin\code.cmd""#,
+            Self::COMMON_EDITOR,
+        ]
+    } else {
+        &[Self::COMMON_EDITOR]
+    };
+
     /// Creates a new [`Finder`].
     #[inline]
     pub fn new() -> Self {
         Default::default()
     }
 
+    /// Creates a [`FinderBuilder`] to configure a [`Finder`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::builder()
+    ///     .extra_environment_variables(["MY_TOOL_EDITOR"])
+    ///     .fallback("nano")
+    ///     .build();
+    /// ```
+    #[inline]
+    pub fn builder() -> FinderBuilder {
+        FinderBuilder::new()
+    }
+
     /// Creates a new [`Finder`] with a set of extra environment variables to look up.
     ///
     /// This can be useful if you're writing an executable and you would also like
@@ -43,11 +239,271 @@ impl Finder {
         S: AsRef<OsStr>,
         I: IntoIterator<Item = S>,
     {
-        let extra_env_vars = extras
-            .into_iter()
-            .map(|s| OsString::from(s.as_ref()))
-            .collect();
-        Self { extra_env_vars }
+        Self::builder().extra_environment_variables(extras).build()
+    }
+
+    /// Creates a new [`Finder`] that looks up the given standard environment variables,
+    /// in order, instead of `$VISUAL` and then `$EDITOR`.
+    ///
+    /// This is useful if your tool prefers `$EDITOR` over `$VISUAL`, or ignores
+    /// `$VISUAL` entirely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::with_standard_environment_variables(["EDITOR"]);
+    /// ```
+    pub fn with_standard_environment_variables<S, I>(keys: I) -> Self
+    where
+        S: AsRef<OsStr>,
+        I: IntoIterator<Item = S>,
+    {
+        Self::builder().standard_environment_variables(keys).build()
+    }
+
+    /// Creates a new [`Finder`] that reads environment variables from `env` instead of
+    /// the process's environment.
+    ///
+    /// See [`EnvSource`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    /// use std::ffi::{OsStr, OsString};
+    ///
+    /// let finder = Finder::with_env_source(|key: &OsStr| {
+    ///     (key == "EDITOR").then(|| OsString::from("nano"))
+    /// });
+    /// assert_eq!("nano", finder.editor_name());
+    /// ```
+    pub fn with_env_source<E>(env: E) -> Self
+    where
+        E: EnvSource + 'static,
+    {
+        Self::builder().env_source(env).build()
+    }
+
+    /// Creates a new [`Finder`] that reads a copy of the environment variables, taken
+    /// now, instead of the current environment.
+    ///
+    /// This makes finding an editor deterministic and thread-safe, even if something
+    /// changes the environment later, like `std::env::set_var`. This can help
+    /// long-running processes. See [`EnvSnapshot`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::from_env_snapshot();
+    /// ```
+    #[inline]
+    pub fn from_env_snapshot() -> Self {
+        Self::with_env_source(EnvSnapshot::capture())
+    }
+
+    /// Creates a new [`Finder`] that falls back to `fallback` instead of `notepad.exe`
+    /// or `vi` when no editor is defined.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::with_fallback("nano");
+    /// ```
+    pub fn with_fallback<S>(fallback: S) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        Self::builder().fallback(fallback).build()
+    }
+
+    /// Finds an editor and reports where it was found.
+    ///
+    /// This can help explain to your users *why* a particular editor was chosen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// let resolved = Finder::new().resolve();
+    /// println!("Using {:?} from {}", resolved.name(), resolved.source());
+    /// ```
+    #[inline]
+    pub fn resolve(&self) -> ResolvedEditor {
+        self.resolve_inner(|key| self.var_os(key))
+    }
+
+    /// Finds an editor and reports where it was found.
+    fn resolve_inner<Env>(&self, f: Env) -> ResolvedEditor
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        let resolved = self.try_resolve_inner(f).unwrap_or_else(|_| {
+            let resolved = ResolvedEditor::new(self.fallback_editor(), EditorSource::Fallback);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(editor = ?resolved.name(), "no editor is configured, using the fallback");
+            #[cfg(feature = "log")]
+            log::debug!(
+                "No editor is configured, using the fallback {:?}",
+                resolved.name()
+            );
+            resolved
+        });
+        debug_assert!(
+            !resolved.name().is_empty(),
+            "An editor should always be found"
+        );
+        resolved
+    }
+
+    /// Finds an editor and reports where it was found, without falling back.
+    fn try_resolve_inner<Env>(&self, mut f: Env) -> Result<ResolvedEditor, Error>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("finder.resolve").entered();
+        let resolved = if self.prefer_context {
+            let context = Context::detect(&mut f);
+            context.pick(self.candidates_inner(&mut f))
+        } else {
+            // NOTE The sources are only read until an editor is found.
+            self.sources.iter().find_map(|source| {
+                self.source_editors(*source, &mut f)
+                    .find(|editor| self.is_candidate(editor))
+            })
+        };
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        if let Some(resolved) = &resolved {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                editor = ?resolved.name(),
+                source = %resolved.source(),
+                "resolved editor"
+            );
+            #[cfg(feature = "log")]
+            log::debug!(
+                "Resolved editor {:?} from {}",
+                resolved.name(),
+                resolved.source()
+            );
+        }
+        resolved.ok_or(Error::NoEditorConfigured)
+    }
+
+    /// Reads an environment variable from this finder's [`EnvSource`].
+    fn var_os(&self, key: &OsStr) -> Option<OsString> {
+        match &self.env {
+            Some(env) => env.var_os(key),
+            None => ProcessEnv.var_os(key),
+        }
+    }
+
+    /// Finds every editor defined by the sources, in order of priority. The fallback
+    /// editors aren't included.
+    ///
+    /// Unlike [`Finder::resolve`], this doesn't stop at the first editor, so it can be
+    /// used to show the user every editor that was detected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// for candidate in Finder::new().candidates() {
+    ///     println!("{:?} from {}", candidate.name(), candidate.source());
+    /// }
+    /// ```
+    #[inline]
+    pub fn candidates(&self) -> impl Iterator<Item = ResolvedEditor> {
+        self.candidates_inner(|key| self.var_os(key))
+    }
+
+    /// Finds every editor defined by the sources, in order of priority.
+    fn candidates_inner<Env>(&self, mut f: Env) -> impl Iterator<Item = ResolvedEditor>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        self.sources
+            .iter()
+            .flat_map(move |source| self.source_editors(*source, &mut f).collect::<Vec<_>>())
+            .filter(|editor| self.is_candidate(editor))
+    }
+
+    /// Checks if a found editor should be used, or skipped in favor of the next one.
+    fn is_candidate(&self, editor: &ResolvedEditor) -> bool {
+        // NOTE Blank values (e.g. `EDITOR=""`) are treated as unset.
+        if is_blank(editor.name()) {
+            return false;
+        }
+        #[cfg(feature = "which")]
+        if self.skip_missing {
+            return self.is_on_path(editor.name());
+        }
+        true
+    }
+
+    /// Finds every editor defined by a single [`Source`], in order. The editors are
+    /// found lazily, so that later ones aren't looked up if they aren't needed.
+    fn source_editors<'a, Env>(
+        &'a self,
+        source: Source,
+        f: &'a mut Env,
+    ) -> Box<dyn Iterator<Item = ResolvedEditor> + 'a>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        match source {
+            Source::Sudo => Box::new(
+                f(OsStr::new("SUDO_EDITOR"))
+                    .map(|name| ResolvedEditor::new(name, EditorSource::SudoEnv))
+                    .into_iter(),
+            ),
+            Source::ExtraEnv => Box::new(self.extra_env_vars.iter().filter_map(|key| {
+                // NOTE We'll just skip if we can't read.
+                let name = f(key)?;
+                Some(ResolvedEditor::new(
+                    name,
+                    EditorSource::ExtraEnv(key.clone()),
+                ))
+            })),
+            Source::Config => Box::new(
+                self.config_value
+                    .iter()
+                    .map(|name| ResolvedEditor::new(name.clone(), EditorSource::Config)),
+            ),
+            Source::Custom => Box::new(self.resolvers.iter().filter_map(|resolver| {
+                let name = resolver.resolve()?;
+                let source = EditorSource::Custom(resolver.name().into());
+                Some(ResolvedEditor::new(name, source))
+            })),
+            Source::StandardEnv => {
+                let skip_visual = self.posix_visual && !context::has_capable_terminal(&mut *f);
+                Box::new(
+                    self.standard_env_vars
+                        .iter()
+                        .filter(move |key| !skip_visual || *key != "VISUAL")
+                        .filter_map(|key| {
+                            let name = f(key)?;
+                            Some(ResolvedEditor::new(
+                                name,
+                                EditorSource::StandardEnv(key.clone()),
+                            ))
+                        }),
+                )
+            }
+            #[cfg(feature = "git")]
+            Source::Git => Box::new(self.find_git_editor(f).into_iter()),
+            Source::Debian => Box::new(self.find_debian_editor(f).into_iter()),
+            #[cfg(feature = "windows-registry")]
+            Source::WindowsRegistry => Box::new(self.find_registry_editor(f).into_iter()),
+        }
     }
 
     /// Gets the name of an editor as a [`String`].
@@ -57,48 +513,61 @@ impl Finder {
     /// [`Finder::which_editor`] to assert that the editor exists in `$PATH`.
     #[inline]
     pub fn editor_name(&self) -> String {
-        self.editor_name_inner(|key| env::var(key), Self::COMMON_EDITOR)
+        self.editor_name_inner(|key| {
+            let value = self.var_os(key).ok_or(())?;
+            value.into_string().map_err(drop)
+        })
     }
 
     /// Gets the name of an editor as a [`String`].
-    fn editor_name_inner<Env, E>(&self, f: Env, fallback: &'static str) -> String
+    fn editor_name_inner<Env, E>(&self, mut f: Env) -> String
     where
-        Env: Copy + FnMut(&OsStr) -> Result<String, E>,
+        Env: FnMut(&OsStr) -> Result<String, E>,
     {
-        let editor = self
-            .find_extra_editor_name(f)
-            .or_else(|| Self::find_editor_name(f))
-            .unwrap_or_else(|| String::from(fallback));
-        debug_assert!(!editor.is_empty(), "An editor should always be found");
-        editor
+        let resolved = self.resolve_inner(|key| f(key).ok().map(OsString::from));
+        resolved
+            .into_name()
+            .into_string()
+            .unwrap_or_else(|name| name.to_string_lossy().into_owned())
     }
 
-    /// Finds the editor [`String`]
-    fn find_editor_name<Env, E>(f: Env) -> Option<String>
-    where
-        Env: FnMut(&OsStr) -> Result<String, E>,
-    {
-        Self::STANDARD_ENV_VARS
-            .into_iter()
-            .map(OsStr::new)
-            .map(f)
-            .filter_map(Result::ok)
-            .next()
+    /// Gets the name of an editor as a [`String`], returning
+    /// [`Error::NoEditorConfigured`] instead of falling back to a default editor.
+    ///
+    /// This is useful if you would rather tell your users to set `$EDITOR` than
+    /// surprise them by opening `vi`. Unlike [`Finder::editor_name`], which skips an
+    /// editor that isn't valid unicode, this returns [`Error::NonUnicodeValue`], so
+    /// that you can tell your users that their variable is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::{Error, Finder};
+    ///
+    /// let finder = Finder::new();
+    /// match finder.try_editor_name() {
+    ///     Ok(editor) => println!("Your editor is {editor}"),
+    ///     Err(Error::NoEditorConfigured) => eprintln!("Please set $EDITOR"),
+    ///     Err(Error::NonUnicodeValue { var }) => eprintln!("Please fix {var}"),
+    ///     Err(e) => eprintln!("{e}"),
+    /// }
+    /// ```
+    #[inline]
+    pub fn try_editor_name(&self) -> Result<String, Error> {
+        self.try_editor_name_inner(|key| self.var_os(key))
     }
 
-    /// Finds the editor [`String`] from any extra environment variable keys that were
-    /// configured.
-    fn find_extra_editor_name<Env, E>(&self, f: Env) -> Option<String>
+    /// Gets the name of an editor as a [`String`] without falling back.
+    fn try_editor_name_inner<Env>(&self, f: Env) -> Result<String, Error>
     where
-        Env: FnMut(&OsStr) -> Result<String, E>,
+        Env: FnMut(&OsStr) -> Option<OsString>,
     {
-        // NOTE We'll just skip if we can't read.
-        self.extra_env_vars
-            .iter()
-            .map(|key| key.as_ref())
-            .map(f)
-            .filter_map(Result::ok)
-            .next()
+        let resolved = self.try_resolve_inner(f)?;
+        let var = resolved.source().to_string();
+        resolved
+            .into_name()
+            .into_string()
+            .map_err(|_| Error::NonUnicodeValue { var })
     }
 
     /// Gets the name of an editor as an [`OsString`].
@@ -107,63 +576,224 @@ impl Finder {
     /// unicode.
     #[inline]
     pub fn editor_name_os(&self) -> OsString {
-        self.editor_name_os_inner(|key| env::var_os(key), Self::COMMON_EDITOR)
+        self.editor_name_os_inner(|key| self.var_os(key))
     }
 
     /// Gets the name of an editor as an [`OsString`].
     ///
     /// This is a lower-level utility in case you expect the editor's name to not be valid
     /// unicode.
-    fn editor_name_os_inner<Env>(&self, f: Env, fallback: &'static str) -> OsString
+    fn editor_name_os_inner<Env>(&self, f: Env) -> OsString
     where
-        Env: Copy + FnMut(&OsStr) -> Option<OsString>,
+        Env: FnMut(&OsStr) -> Option<OsString>,
     {
-        let editor = self
-            .find_extra_editor_name_os(f)
-            .or_else(|| Self::find_editor_name_os(f))
-            .unwrap_or_else(|| OsString::from(fallback));
-        debug_assert!(!editor.is_empty(), "An editor should always be found");
-        editor
+        self.resolve_inner(f).into_name()
     }
 
-    /// Finds the editor [`OsString`] from any extra environment variable keys that were
-    /// configured.
-    fn find_extra_editor_name_os<Env>(&self, f: Env) -> Option<OsString>
-    where
-        Env: FnMut(&OsStr) -> Option<OsString>,
-    {
-        // NOTE We'll just skip if we can't read.
-        self.extra_env_vars
-            .iter()
-            .map(|key| key.as_ref())
-            .filter_map(f)
-            .next()
+    /// Gets the name of an editor as an [`OsString`], returning
+    /// [`Error::NoEditorConfigured`] instead of falling back to a default editor.
+    ///
+    /// See [`Finder::try_editor_name`] for more information.
+    #[inline]
+    pub fn try_editor_name_os(&self) -> Result<OsString, Error> {
+        self.try_editor_name_os_inner(|key| self.var_os(key))
     }
 
-    /// Finds the editor [`OsString`].
-    fn find_editor_name_os<Env>(f: Env) -> Option<OsString>
+    /// Gets the name of an editor as an [`OsString`] without falling back.
+    fn try_editor_name_os_inner<Env>(&self, f: Env) -> Result<OsString, Error>
     where
         Env: FnMut(&OsStr) -> Option<OsString>,
     {
-        Self::STANDARD_ENV_VARS
-            .into_iter()
-            .map(OsStr::new)
-            .filter_map(f)
-            .next()
+        self.try_resolve_inner(f).map(ResolvedEditor::into_name)
+    }
+
+    /// Picks the fallback editor.
+    ///
+    /// With the `which` feature, this is the first fallback that can be found on
+    /// `$PATH`, or the last fallback if none of them can be found. Otherwise, this is
+    /// the first fallback.
+    fn fallback_editor(&self) -> Cow<'_, OsStr> {
+        if let Some(editor) = self.vscode_fallback() {
+            return Cow::Borrowed(editor);
+        }
+        #[cfg(feature = "which")]
+        if let Some(fallback) = self.fallbacks.iter().find(|f| self.is_on_path(f)) {
+            return self.fallback_name(fallback);
+        }
+        // NOTE The last fallback is the least preferred, but the most likely to be
+        //      installed, like Notepad after editors that were just shown not to be.
+        #[cfg(feature = "which")]
+        let fallback = self.fallbacks.last();
+        #[cfg(not(feature = "which"))]
+        let fallback = self.fallbacks.first();
+        let fallback = fallback
+            .map(|fallback| fallback.as_os_str())
+            .unwrap_or_else(|| OsStr::new(Self::COMMON_EDITOR));
+        self.fallback_name(fallback)
+    }
+
+    /// Gets the name of a `fallback` editor.
+    ///
+    /// With the Windows split style, a fallback that's a single quoted path, like
+    /// `"%ProgramFiles%\Notepad++\notepad++.exe"`, is expanded to the path, so that it
+    /// can be run as a command. If the path exists, it isn't split again.
+    #[cfg(feature = "which")]
+    fn fallback_name<'a>(&self, fallback: &'a OsStr) -> Cow<'a, OsStr> {
+        if self.split_style != SplitStyle::Windows {
+            return Cow::Borrowed(fallback);
+        }
+        let words = fallback.to_str().and_then(|fallback| {
+            expand::split_expand(fallback, self.split_style, |key| self.var_os(key)).ok()
+        });
+        match words {
+            Some(words) if words.len() == 1 => Cow::Owned(words.concat().into()),
+            _ => Cow::Borrowed(fallback),
+        }
+    }
+
+    /// Gets the name of a `fallback` editor. Without the `which` feature, fallbacks
+    /// aren't expanded, because they're only paths with the `which` feature.
+    #[cfg(not(feature = "which"))]
+    fn fallback_name<'a>(&self, fallback: &'a OsStr) -> Cow<'a, OsStr> {
+        Cow::Borrowed(fallback)
+    }
+
+    /// Gets VS Code as the fallback editor, if it's enabled with
+    /// [`FinderBuilder::vscode_terminal`] and this is running in VS Code's integrated
+    /// terminal. With the `which` feature, `code` must also be on `$PATH`.
+    fn vscode_fallback(&self) -> Option<&'static OsStr> {
+        /// VS Code, waiting for files to be closed.
+        const VSCODE: &str = "code --wait";
+
+        if !self.vscode_terminal {
+            return None;
+        }
+        let in_vscode = self
+            .var_os(OsStr::new("TERM_PROGRAM"))
+            .is_some_and(|program| program == "vscode")
+            || self.var_os(OsStr::new("VSCODE_GIT_IPC_HANDLE")).is_some();
+        #[cfg(feature = "which")]
+        let in_vscode = in_vscode && self.is_on_path(OsStr::new(VSCODE));
+        in_vscode.then(|| OsStr::new(VSCODE))
+    }
+
+    /// Checks if an editor's command can be found on `$PATH`.
+    #[cfg(feature = "which")]
+    fn is_on_path(&self, editor: &OsStr) -> bool {
+        self.editor_command(editor)
+            .is_some_and(|command| self.which(&command).is_ok())
+    }
+
+    /// Gets the command of an editor, which is its first word, or `None` if it can't
+    /// be split.
+    #[cfg(feature = "which")]
+    fn editor_command(&self, editor: &OsStr) -> Option<OsString> {
+        match editor.to_str() {
+            Some(editor) => {
+                let words =
+                    expand::split_expand(editor, self.split_style, |key| self.var_os(key)).ok()?;
+                words.into_iter().next().map(OsString::from)
+            }
+            None => Some(OsString::from(editor)),
+        }
     }
 
     /// Finds the editor with [`Finder::editor_name`], then splits the editor into its
     /// command and any arguments.
     ///
-    /// This can be useful when the editor includes arguments, like `code --wait`.
+    /// This can be useful when the editor includes arguments, like `code --wait`. A
+    /// leading `~` in each word is expanded to the home directory, and `$VAR` and
+    /// `${VAR}` references (or `%VAR%` on Windows) are expanded to the variables'
+    /// values, so `~/bin/myedit` and `$HOME/tools/edit --fast` work. Like in a shell,
+    /// they aren't expanded when they're single-quoted or escaped, like `'$HOME'`.
+    ///
+    /// The editor is split with the platform's [`SplitStyle`] unless
+    /// [`FinderBuilder::split_style`] overrides it. With [`SplitStyle::Windows`], an
+    /// editor that's the absolute path to an existing file isn't split, even if it has
+    /// spaces.
     #[cfg(feature = "split")]
+    #[inline]
     pub fn split_editor_name(&self) -> Result<(String, Vec<String>), Error> {
-        let editor = self.editor_name();
-        let words = shell_words::split(&editor).map_err(Error::ShellWords)?;
+        self.split_editor_name_inner(|key| self.var_os(key))
+    }
+
+    /// Finds the editor, then splits and expands it into its command and arguments.
+    #[cfg(feature = "split")]
+    fn split_editor_name_inner<Env>(&self, f: Env) -> Result<(String, Vec<String>), Error>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        let into_string = |word: OsString| {
+            word.into_string()
+                .unwrap_or_else(|word| word.to_string_lossy().into_owned())
+        };
+        let (editor, args) = self.split_editor_name_os_inner(f)?;
+        Ok((
+            into_string(editor),
+            args.into_iter().map(into_string).collect(),
+        ))
+    }
+
+    /// Finds the editor with [`Finder::editor_name_os`], then splits the editor into its
+    /// command and any arguments, without losing bytes that aren't valid UTF-8.
+    ///
+    /// See [`Finder::split_editor_name`] for more information.
+    #[cfg(feature = "split")]
+    #[inline]
+    pub fn split_editor_name_os(&self) -> Result<(OsString, Vec<OsString>), Error> {
+        self.split_editor_name_os_inner(|key| self.var_os(key))
+    }
+
+    /// Finds the editor, then splits and expands it into its command and arguments as
+    /// [`OsString`]s.
+    #[cfg(feature = "split")]
+    fn split_editor_name_os_inner<Env>(
+        &self,
+        mut f: Env,
+    ) -> Result<(OsString, Vec<OsString>), Error>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        let editor = self.resolve_inner(&mut f).into_name();
+        self.split_editor(&editor, f)
+    }
+
+    /// Splits and expands an editor into its command and arguments as [`OsString`]s.
+    #[cfg(feature = "split")]
+    fn split_editor<Env>(
+        &self,
+        editor: &OsStr,
+        mut f: Env,
+    ) -> Result<(OsString, Vec<OsString>), Error>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("finder.split", ?editor).entered();
+        // NOTE Windows paths often have spaces, like C:\Program Files, and aren't always
+        //      quoted.
+        if self.split_style == SplitStyle::Windows {
+            let path = std::path::Path::new(editor);
+            if path.is_absolute() && path.is_file() {
+                return Ok((editor.to_owned(), Vec::new()));
+            }
+        }
+        // NOTE Values are encoded too, so that they keep their bytes after decoding.
+        let f = |key: &OsStr| f(key).map(|value| OsString::from(split::encode(&value)));
+        let words = expand::split_expand(&split::encode(editor), self.split_style, f).map_err(
+            |source| Error::InvalidEditor {
+                editor: editor.to_string_lossy().into_owned(),
+                source,
+            },
+        )?;
         debug_assert!(!words.is_empty(), "There should always be at least 1 word");
-        let mut words = words.into_iter();
+        let mut words = words.into_iter().map(split::decode);
         let editor = words.next().expect("A command name should be present");
         let args = words.collect::<Vec<_>>();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(command = ?editor, ?args, "split editor");
+        #[cfg(feature = "log")]
+        log::debug!("Split editor into {editor:?} with arguments {args:?}");
         Ok((editor, args))
     }
 
@@ -174,6 +804,20 @@ impl Finder {
     /// would intentionally want to run a binary in the current directory, and can be a
     /// security issue.
     ///
+    /// If the command is a path, like `/usr/local/bin/customedit` or `./bin/edit`,
+    /// `$PATH` isn't searched. Instead, the file must exist and be executable. See
+    /// [`FinderBuilder::relative_editor_paths`] to forbid relative paths.
+    ///
+    /// On Windows, a command without an extension is searched for with each extension
+    /// in `%PATHEXT%`. Microsoft Store App Execution Aliases in
+    /// `%LOCALAPPDATA%\Microsoft\WindowsApps` are also found, even if they aren't on
+    /// `%PATH%`.
+    ///
+    /// On Linux, an editor that isn't on `$PATH` can be a Flatpak app, like
+    /// `org.gnome.TextEditor` or a well-known editor like `codium`, or a Snap in
+    /// `/snap/bin`. Then the path to `flatpak` or `snap` is returned, and the arguments
+    /// start with `run` and the app.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -184,17 +828,369 @@ impl Finder {
     /// ```
     #[cfg(feature = "which")]
     pub fn which_editor(&self) -> Result<(PathBuf, Vec<String>), Error> {
-        use which::which;
+        let (path, args) = self.which_editor_os()?;
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                arg.into_string()
+                    .unwrap_or_else(|arg| arg.to_string_lossy().into_owned())
+            })
+            .collect();
+        Ok((path, args))
+    }
 
-        let (editor, args) = self.split_editor_name()?;
-        let editor = which(editor).map_err(Error::Which)?;
-        Ok((editor, args))
+    /// Finds the editor's command with [`Finder::split_editor_name_os`], then finds the
+    /// editor command's path, without losing bytes that aren't valid UTF-8.
+    ///
+    /// See [`Finder::which_editor`] for more information.
+    #[cfg(feature = "which")]
+    pub fn which_editor_os(&self) -> Result<(PathBuf, Vec<OsString>), Error> {
+        let Some(cache) = &self.cache else {
+            return self.find_editor_command();
+        };
+        let mut cached = cache.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(command) = cached.as_ref() {
+            return Ok(command.clone());
+        }
+        let command = self.find_editor_command()?;
+        *cached = Some(command.clone());
+        Ok(command)
+    }
+
+    /// Finds the editor's command and its path like [`Finder::which_editor_os`], then
+    /// resolves any symbolic links in the path to find the real executable.
+    ///
+    /// For example, `/usr/bin/editor` can be a link to `/etc/alternatives/editor`, which
+    /// is a link to `/usr/bin/vim.basic`. The real executable identifies the editor,
+    /// like with [`EditorInfo::for_command`](crate::EditorInfo::for_command), but
+    /// shouldn't be run instead of the path from [`Finder::which_editor_os`], because
+    /// some programs, like `busybox`, behave differently depending on the name they're
+    /// run with. If the path can't be resolved, it's returned as-is.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::{EditorInfo, Finder};
+    ///
+    /// let (path, _) = Finder::new()
+    ///     .which_editor_canonical()
+    ///     .expect("Should find an editor");
+    /// if let Some(info) = EditorInfo::for_command(&path) {
+    ///     println!("The editor is {}", info.name);
+    /// }
+    /// ```
+    #[cfg(feature = "which")]
+    pub fn which_editor_canonical(&self) -> Result<(PathBuf, Vec<OsString>), Error> {
+        let (path, args) = self.which_editor_os()?;
+        let path = std::fs::canonicalize(&path).unwrap_or(path);
+        Ok((path, args))
+    }
+
+    /// Finds every path of the editor's command on `$PATH`, in `$PATH` order, like
+    /// `which -a`. The first path is the one that [`Finder::which_editor`] finds.
+    ///
+    /// This shows when the editor's command is shadowed by another command with the
+    /// same name earlier on `$PATH`. If the command is a path, it's the only path
+    /// returned. Flatpak apps and Snaps aren't included, and this doesn't use the
+    /// [cache](Finder::cached).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let paths = Finder::new().which_editor_all().expect("Should find an editor");
+    /// if let [used, shadowed @ ..] = paths.as_slice() {
+    ///     println!("Using {}", used.display());
+    ///     for path in shadowed {
+    ///         println!("Shadowing {}", path.display());
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "which")]
+    pub fn which_editor_all(&self) -> Result<Vec<PathBuf>, Error> {
+        let (editor, _) = self.split_editor_name_os()?;
+        let first = self.find_command(&editor)?;
+        let command = Path::new(&editor);
+        if command.components().count() != 1 || command.has_root() {
+            return Ok(vec![first]);
+        }
+        let paths = self.path_var();
+        let mut all = which::which_in_all(&editor, paths, Path::new(""))
+            .map(Iterator::collect::<Vec<_>>)
+            .unwrap_or_default();
+        // NOTE The command can be found outside of $PATH, like in a login shell.
+        if !all.contains(&first) {
+            all.insert(0, first);
+        }
+        Ok(all)
+    }
+
+    /// Makes this finder remember the editor's command after it is first found by
+    /// [`Finder::which_editor_os`], which is also used to open editors.
+    ///
+    /// This avoids reading environment variables and searching `$PATH` every time an
+    /// editor is opened, like in a TUI that opens editors repeatedly. Use
+    /// [`Finder::invalidate`] to find the editor again, like after the user changes
+    /// their settings. Errors aren't remembered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new().cached();
+    /// // Only the first call searches $PATH.
+    /// for file in ["a.txt", "b.txt"] {
+    ///     finder.open_editor(file, true).expect("Should open an editor");
+    /// }
+    /// ```
+    #[cfg(feature = "which")]
+    pub fn cached(mut self) -> Self {
+        self.cache = Some(Default::default());
+        self
+    }
+
+    /// Forgets the editor's command remembered by a [`Finder::cached`] finder, so that
+    /// it's found again. This does nothing if the finder isn't cached.
+    #[cfg(feature = "which")]
+    pub fn invalidate(&self) {
+        if let Some(cache) = &self.cache {
+            *cache.lock().unwrap_or_else(PoisonError::into_inner) = None;
+        }
+    }
+
+    /// Finds the editor's command and its path without using the cache.
+    #[cfg(feature = "which")]
+    fn find_editor_command(&self) -> Result<EditorCommand, Error> {
+        let (editor, args) = self.split_editor_name_os()?;
+        let path = match self.find_command(&editor) {
+            Ok(path) => path,
+            Err(e) => {
+                let (path, run_args) = packaged::find(self, &editor).ok_or(e)?;
+                return Ok((path, run_args.into_iter().chain(args).collect()));
+            }
+        };
+        Ok((path, args))
+    }
+
+    /// Gets the `$PATH` to find commands on, which can be set with
+    /// [`FinderBuilder::search_path`].
+    #[cfg(feature = "which")]
+    fn path_var(&self) -> Option<OsString> {
+        self.search_path
+            .clone()
+            .or_else(|| self.var_os(OsStr::new("PATH")))
+    }
+
+    /// Finds a command on the `$PATH` from this finder's [`EnvSource`].
+    #[cfg(feature = "which")]
+    fn which(&self, command: &OsStr) -> which::Result<PathBuf> {
+        // NOTE The current directory is only used for paths, which aren't searched for.
+        let paths = self.path_var();
+        let path = which::which_in(command, paths, Path::new(""));
+        // NOTE A search path is hermetic, so commands aren't found outside of it.
+        if self.search_path.is_some() {
+            return path;
+        }
+        #[cfg(unix)]
+        if path.is_err() && self.login_shell {
+            let shell = self.var_os(OsStr::new("SHELL"));
+            if let Some(path) = shell.and_then(|shell| login_shell::find(&shell, command)) {
+                return Ok(path);
+            }
+        }
+        #[cfg(windows)]
+        if path.is_err() {
+            let local_app_data = self.var_os(OsStr::new("LOCALAPPDATA"));
+            let pathext = self.var_os(OsStr::new("PATHEXT"));
+            if let Some(path) = windows_apps::find(command, local_app_data, pathext) {
+                return Ok(path);
+            }
+        }
+        path
+    }
+
+    /// Finds the path to an editor's command, and traces the result.
+    #[cfg(feature = "which")]
+    fn find_command(&self, command: &OsStr) -> Result<PathBuf, Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("finder.which", ?command).entered();
+        let path = self.find_command_path(command);
+        #[cfg(feature = "tracing")]
+        match &path {
+            Ok(path) => tracing::debug!(path = %path.display(), "found editor"),
+            Err(error) => tracing::debug!(%error, "editor not found"),
+        }
+        #[cfg(feature = "log")]
+        match &path {
+            Ok(path) => log::debug!("Found editor {command:?} at {}", path.display()),
+            Err(error) => log::debug!("Editor {command:?} not found: {error}"),
+        }
+        path
+    }
+
+    /// Finds the path to an editor's command.
+    #[cfg(feature = "which")]
+    fn find_command_path(&self, command: &OsStr) -> Result<PathBuf, Error> {
+        let path = Path::new(command);
+        // NOTE Paths aren't searched for, so they can't resolve to something else.
+        if path.components().count() == 1 && !path.has_root() {
+            let name = command.to_string_lossy();
+            return self.which(command).map_err(|source| Error::EditorNotFound {
+                suggestion: suggest::suggest(&name, self.path_var()),
+                command: name.into_owned(),
+                source,
+            });
+        }
+        if path.is_relative() && !self.allow_relative_paths {
+            return Err(Error::RelativeEditorPath(path.to_path_buf()));
+        }
+        if !is_executable(path) {
+            return Err(Error::EditorNotFound {
+                command: command.to_string_lossy().into_owned(),
+                suggestion: None,
+                source: which::Error::CannotFindBinaryPath,
+            });
+        }
+        Ok(path.to_path_buf())
+    }
+}
+
+impl fmt::Debug for Finder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let resolvers = self
+            .resolvers
+            .iter()
+            .map(|resolver| resolver.name())
+            .collect::<Vec<_>>();
+        let mut debug = f.debug_struct("Finder");
+        debug
+            .field("custom_env", &self.env.is_some())
+            .field("extra_env_vars", &self.extra_env_vars)
+            .field("config_value", &self.config_value)
+            .field("resolvers", &resolvers)
+            .field("standard_env_vars", &self.standard_env_vars)
+            .field("fallbacks", &self.fallbacks)
+            .field("sources", &self.sources)
+            .field("prefer_context", &self.prefer_context)
+            .field("posix_visual", &self.posix_visual)
+            .field("vscode_terminal", &self.vscode_terminal);
+        #[cfg(feature = "split")]
+        debug.field("split_style", &self.split_style);
+        #[cfg(feature = "which")]
+        debug
+            .field("skip_missing", &self.skip_missing)
+            .field("allow_relative_paths", &self.allow_relative_paths)
+            .field("login_shell", &self.login_shell)
+            .field("search_path", &self.search_path)
+            .field("cached", &self.cache.is_some());
+        debug.finish()
+    }
+}
+
+impl PartialEq for Finder {
+    fn eq(&self, other: &Self) -> bool {
+        let same_env = match (&self.env, &other.env) {
+            (Some(env), Some(other)) => Arc::ptr_eq(env, other),
+            (env, other) => env.is_none() && other.is_none(),
+        };
+        let same_resolvers = self.resolvers.len() == other.resolvers.len()
+            && self
+                .resolvers
+                .iter()
+                .zip(&other.resolvers)
+                .all(|(resolver, other)| Arc::ptr_eq(resolver, other));
+        #[cfg(feature = "split")]
+        let same_split = self.split_style == other.split_style;
+        #[cfg(not(feature = "split"))]
+        let same_split = true;
+        #[cfg(feature = "which")]
+        let same_which = self.skip_missing == other.skip_missing
+            && self.allow_relative_paths == other.allow_relative_paths
+            && self.login_shell == other.login_shell
+            && self.search_path == other.search_path
+            && self.cache.is_some() == other.cache.is_some();
+        #[cfg(not(feature = "which"))]
+        let same_which = true;
+        same_env
+            && same_resolvers
+            && same_split
+            && same_which
+            && self.extra_env_vars == other.extra_env_vars
+            && self.config_value == other.config_value
+            && self.standard_env_vars == other.standard_env_vars
+            && self.fallbacks == other.fallbacks
+            && self.sources == other.sources
+            && self.prefer_context == other.prefer_context
+            && self.posix_visual == other.posix_visual
+            && self.vscode_terminal == other.vscode_terminal
     }
 }
 
+impl Eq for Finder {}
+
+impl Default for Finder {
+    fn default() -> Self {
+        Self {
+            env: None,
+            extra_env_vars: Vec::new(),
+            config_value: None,
+            resolvers: Vec::new(),
+            standard_env_vars: Self::STANDARD_ENV_VARS.map(OsString::from).into(),
+            fallbacks: Self::DEFAULT_FALLBACKS.iter().map(OsString::from).collect(),
+            sources: Source::DEFAULT.into(),
+            prefer_context: false,
+            posix_visual: false,
+            vscode_terminal: false,
+            #[cfg(feature = "split")]
+            split_style: SplitStyle::default(),
+            #[cfg(feature = "which")]
+            skip_missing: false,
+            #[cfg(feature = "which")]
+            allow_relative_paths: true,
+            #[cfg(feature = "which")]
+            login_shell: false,
+            #[cfg(feature = "which")]
+            search_path: None,
+            #[cfg(feature = "which")]
+            cache: None,
+        }
+    }
+}
+
+/// Checks if a path is an executable file.
+#[cfg(feature = "which")]
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        // NOTE App Execution Aliases can't be followed, but can be run.
+        #[cfg(windows)]
+        return windows_apps::is_alias(path);
+        #[cfg(not(windows))]
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+/// Checks if an editor's name is empty or only whitespace.
+fn is_blank(name: &OsStr) -> bool {
+    name.to_string_lossy().trim().is_empty()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(unix)]
+    use crate::finder::test_utils::create_executable;
     use rstest::rstest;
 
     const FALLBACK: &str = "fallback";
@@ -217,8 +1213,11 @@ mod tests {
                 .then_some(String::from(editor_name))
                 .ok_or(())
         };
-        let finder = Finder::with_extra_environment_variables(extra_keys);
-        let actual = finder.editor_name_inner(f, FALLBACK);
+        let finder = Finder::builder()
+            .extra_environment_variables(extra_keys)
+            .fallback(FALLBACK)
+            .build();
+        let actual = finder.editor_name_inner(f);
         assert_eq!(expected, actual);
     }
 
@@ -236,8 +1235,633 @@ mod tests {
         Extras: IntoIterator<Item = &'static str>,
     {
         let f = |key: &OsStr| (key == defined_key).then_some(OsString::from(editor_name));
-        let finder = Finder::with_extra_environment_variables(extra_keys);
-        let actual = finder.editor_name_os_inner(f, FALLBACK);
+        let finder = Finder::builder()
+            .extra_environment_variables(extra_keys)
+            .fallback(FALLBACK)
+            .build();
+        let actual = finder.editor_name_os_inner(f);
+        assert_eq!(expected, actual);
+    }
+
+    #[rstest]
+    #[case::default_order(None, "visual")]
+    #[case::editor_first(Some(["EDITOR", "VISUAL"]), "editor")]
+    #[case::none_defined(Some(["--UNKNOWN--", "--OTHER--"]), FALLBACK)]
+    fn test_builder_standard_environment_variables(
+        #[case] standard_keys: Option<[&str; 2]>,
+        #[case] expected: &str,
+    ) {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("VISUAL") => Some(OsString::from("visual")),
+            Some("EDITOR") => Some(OsString::from("editor")),
+            _ => None,
+        };
+        let builder = Finder::builder().fallback(FALLBACK);
+        let builder = match standard_keys {
+            Some(keys) => builder.standard_environment_variables(keys),
+            None => builder,
+        };
+        let finder = builder.build();
+        let actual = finder.editor_name_os_inner(f);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_with_standard_environment_variables() {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("VISUAL") => Some(OsString::from("visual")),
+            Some("EDITOR") => Some(OsString::from("editor")),
+            _ => None,
+        };
+        let finder = Finder::with_standard_environment_variables(["EDITOR"]);
+        let actual = finder.editor_name_os_inner(f);
+        assert_eq!("editor", actual);
+    }
+
+    #[cfg(all(unix, feature = "which"))]
+    #[rstest]
+    #[case::first_found(["sh", "--NOT-AN-EDITOR--"], "sh")]
+    #[case::skips_missing(["--NOT-AN-EDITOR--", "sh -i"], "sh -i")]
+    #[case::none_found(["--NOT-AN-EDITOR--", "--OTHER--"], "--OTHER--")]
+    fn test_builder_fallbacks(#[case] fallbacks: [&str; 2], #[case] expected: &str) {
+        let finder = Finder::builder()
+            .standard_environment_variables::<&str, _>([])
+            .fallbacks(fallbacks)
+            .build();
+        let actual = finder.editor_name_os_inner(|_| None);
+        assert_eq!(expected, actual);
+    }
+
+    #[rstest]
+    #[case::visual_defined("VISUAL", Some("foo"))]
+    #[case::extra_defined("MY_EXTRA", Some("foo"))]
+    #[case::unknown_key_defined("--UNKNOWN--", None)]
+    fn test_try_editor_name(#[case] defined_key: &str, #[case] expected: Option<&str>) {
+        let f = |key: &OsStr| (key == defined_key).then_some(OsString::from("foo"));
+        let finder = Finder::with_extra_environment_variables(["MY_EXTRA"]);
+        let actual = finder.try_editor_name_os_inner(f);
+        match expected {
+            Some(expected) => assert_eq!(expected, actual.unwrap()),
+            None => assert!(matches!(actual, Err(Error::NoEditorConfigured))),
+        }
+    }
+
+    #[rstest]
+    #[case::configured(Some("nano"), EditorSource::Config)]
+    #[case::not_configured(None, EditorSource::StandardEnv("EDITOR".into()))]
+    fn test_builder_config_value(#[case] config: Option<&str>, #[case] expected: EditorSource) {
+        let f = |key: &OsStr| (key == "EDITOR").then(|| OsString::from("vim"));
+        let finder = Finder::builder().config_value(config).build();
+        let actual = finder.resolve_inner(f);
+        assert_eq!(&expected, actual.source());
+    }
+
+    #[test]
+    fn test_builder_config_value_priority() {
+        let f = |key: &OsStr| (key == "EDITOR").then(|| OsString::from("vim"));
+        let finder = Finder::builder()
+            .config_value(Some("nano"))
+            .sources([Source::StandardEnv, Source::Config])
+            .build();
+        let actual = finder.resolve_inner(f);
+        assert_eq!(&EditorSource::StandardEnv("EDITOR".into()), actual.source());
+    }
+
+    #[test]
+    fn test_eq() {
+        let finder = Finder::builder()
+            .resolver(|| Some(OsString::from("nano")))
+            .build();
+        assert_eq!(finder, finder.clone());
+        assert_eq!(Finder::new(), Finder::new());
+        assert_ne!(Finder::new(), Finder::with_fallback(FALLBACK));
+        assert_ne!(finder, Finder::new());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let finder = Finder::builder()
+            .extra_environment_variables(["MY_TOOL_EDITOR"])
+            .fallback("nano")
+            .config_value(Some("code --wait"))
+            .build();
+        let json = serde_json::to_value(&finder).unwrap();
+        assert_eq!(
+            serde_json::json!(["MY_TOOL_EDITOR"]),
+            json["extra_env_vars"]
+        );
+        assert_eq!(serde_json::json!("code --wait"), json["config_value"]);
+        assert_eq!(finder, serde_json::from_value(json).unwrap());
+
+        let partial: Finder = serde_json::from_str(r#"{"fallbacks": ["nano"]}"#).unwrap();
+        assert_eq!(Finder::with_fallback("nano"), partial);
+    }
+
+    #[test]
+    fn test_debug() {
+        let finder = Finder::builder()
+            .resolver(|| Some(OsString::from("nano")))
+            .build();
+        let debug = format!("{finder:?}");
+        assert!(debug.contains(r#"resolvers: ["custom resolver"]"#));
+    }
+
+    #[test]
+    fn test_with_env_source() {
+        let env =
+            std::collections::HashMap::from([(OsString::from("EDITOR"), OsString::from("nano"))]);
+        let finder = Finder::with_env_source(env);
+        let actual = finder.resolve();
+        assert_eq!("nano", actual.name());
+        assert_eq!(&EditorSource::StandardEnv("EDITOR".into()), actual.source());
+    }
+
+    #[test]
+    fn test_candidates() {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("MY_EXTRA") => Some(OsString::from("extra")),
+            Some("VISUAL") => Some(OsString::from("")),
+            Some("EDITOR") => Some(OsString::from("editor")),
+            _ => None,
+        };
+        let finder = Finder::builder()
+            .extra_environment_variables(["MY_EXTRA"])
+            .fallback(FALLBACK)
+            .build();
+        let actual = finder
+            .candidates_inner(f)
+            .map(|candidate| candidate.source().clone())
+            .collect::<Vec<_>>();
+        let expected = vec![
+            EditorSource::ExtraEnv("MY_EXTRA".into()),
+            EditorSource::StandardEnv("EDITOR".into()),
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_try_editor_name_non_unicode() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let f = |key: &OsStr| match key.to_str() {
+            Some("VISUAL") => Some(OsStr::from_bytes(b"caf\xe9").to_owned()),
+            Some("EDITOR") => Some(OsString::from("vim")),
+            _ => None,
+        };
+        let finder = Finder::new();
+        let Err(Error::NonUnicodeValue { var }) = finder.try_editor_name_inner(f) else {
+            panic!("$VISUAL should not be valid unicode");
+        };
+        assert_eq!("$VISUAL", var);
+    }
+
+    #[rstest]
+    #[case::visual_defined("VISUAL", EditorSource::StandardEnv("VISUAL".into()))]
+    #[case::extra_defined("MY_EXTRA", EditorSource::ExtraEnv("MY_EXTRA".into()))]
+    #[case::unknown_key_defined("--UNKNOWN--", EditorSource::Fallback)]
+    fn test_resolve(#[case] defined_key: &str, #[case] expected: EditorSource) {
+        let f = |key: &OsStr| (key == defined_key).then_some(OsString::from("foo"));
+        let finder = Finder::with_extra_environment_variables(["MY_EXTRA"]);
+        let actual = finder.resolve_inner(f);
+        assert_eq!(&expected, actual.source());
+    }
+
+    #[rstest]
+    #[case::empty("", EditorSource::StandardEnv("EDITOR".into()))]
+    #[case::whitespace(" \t", EditorSource::StandardEnv("EDITOR".into()))]
+    #[case::not_blank("foo", EditorSource::StandardEnv("VISUAL".into()))]
+    fn test_resolve_blank(#[case] visual: &str, #[case] expected: EditorSource) {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("VISUAL") => Some(OsString::from(visual)),
+            Some("EDITOR") => Some(OsString::from("editor")),
+            _ => None,
+        };
+        let actual = Finder::new().resolve_inner(f);
+        assert_eq!(&expected, actual.source());
+    }
+
+    #[cfg(feature = "which")]
+    #[rstest]
+    #[case::skip(true, EditorSource::StandardEnv("EDITOR".into()))]
+    #[case::keep(false, EditorSource::StandardEnv("VISUAL".into()))]
+    fn test_builder_skip_missing_editors(#[case] skip: bool, #[case] expected: EditorSource) {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("VISUAL") => Some(OsString::from("--NOT-AN-EDITOR-- --wait")),
+            Some("EDITOR") => Some(OsString::from("sh")),
+            _ => None,
+        };
+        let finder = Finder::builder().skip_missing_editors(skip).build();
+        let actual = finder.resolve_inner(f);
+        assert_eq!(&expected, actual.source());
+    }
+
+    #[cfg(feature = "split")]
+    #[test]
+    fn test_split_editor_name_expands() {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("EDITOR") => Some(OsString::from(r#""$TOOLS/my edit" --fast ~/notes"#)),
+            Some("TOOLS") => Some(OsString::from("/opt/tools")),
+            Some("HOME" | "USERPROFILE") => Some(OsString::from("/home/me")),
+            _ => None,
+        };
+        let (editor, args) = Finder::new().split_editor_name_inner(f).unwrap();
+        assert_eq!("/opt/tools/my edit", editor);
+        assert_eq!(vec!["--fast", "/home/me/notes"], args);
+    }
+
+    #[cfg(feature = "split")]
+    #[rstest]
+    #[case::posix(SplitStyle::Posix, "C:Windowsnotepad.exe")]
+    #[case::windows(SplitStyle::Windows, r"C:\Windows\notepad.exe")]
+    fn test_builder_split_style(#[case] style: SplitStyle, #[case] expected: &str) {
+        let f =
+            |key: &OsStr| (key == "EDITOR").then(|| OsString::from(r"C:\Windows\notepad.exe -n"));
+        let finder = Finder::builder().split_style(style).build();
+        let (editor, args) = finder.split_editor_name_inner(f).unwrap();
+        assert_eq!(expected, editor);
+        assert_eq!(vec!["-n"], args);
+    }
+
+    #[cfg(feature = "split")]
+    #[test]
+    fn test_split_editor_name_invalid() {
+        use std::error::Error as _;
+
+        let f = |key: &OsStr| (key == "EDITOR").then(|| OsString::from(r#"vim "notes"#));
+        let Err(e) = Finder::new().split_editor_name_inner(f) else {
+            panic!("The editor shouldn't be split");
+        };
+        assert!(matches!(&e, Error::InvalidEditor { editor, .. } if editor == r#"vim "notes"#));
+        assert!(e.source().is_some());
+    }
+
+    #[cfg(all(feature = "split", unix))]
+    #[test]
+    fn test_split_editor_name_os() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let f = |key: &OsStr| match key.to_str() {
+            Some("EDITOR") => Some(OsString::from("$DIR/edit --fast")),
+            Some("DIR") => Some(OsStr::from_bytes(b"/opt/caf\xe9").to_owned()),
+            _ => None,
+        };
+        let (editor, args) = Finder::new().split_editor_name_os_inner(f).unwrap();
+        assert_eq!(OsStr::from_bytes(b"/opt/caf\xe9/edit"), editor);
+        assert_eq!(vec!["--fast"], args);
+    }
+
+    #[cfg(all(feature = "which", unix))]
+    #[test]
+    fn test_cached() {
+        let env = |key: &OsStr| match key.to_str() {
+            Some("EDITOR") => Some(OsString::from("sh -n")),
+            Some("PATH") => std::env::var_os("PATH"),
+            _ => None,
+        };
+        let finder = Finder::builder().env_source(env).build().cached();
+        let (path, args) = finder.which_editor_os().unwrap();
+        assert_eq!(Some(OsStr::new("sh")), path.file_name());
+        assert_eq!(vec!["-n"], args);
+        let cached = finder.cache.as_ref().unwrap().lock().unwrap().clone();
+        assert_eq!(Some((path, args)), cached);
+        finder.invalidate();
+        assert_eq!(None, *finder.cache.as_ref().unwrap().lock().unwrap());
+    }
+
+    #[cfg(all(feature = "which", unix))]
+    #[test]
+    fn test_which_editor_all() {
+        let dirs = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+        for dir in &dirs {
+            let path = dir.path().join("my-editor");
+            create_executable(&path);
+        }
+        let path = std::env::join_paths(dirs.iter().map(|dir| dir.path())).unwrap();
+        let env = [
+            ("EDITOR", OsString::from("my-editor --wait")),
+            ("PATH", path),
+        ]
+        .into_iter()
+        .collect::<EnvSnapshot>();
+        let actual = Finder::with_env_source(env).which_editor_all().unwrap();
+        let expected = dirs
+            .iter()
+            .map(|dir| dir.path().join("my-editor"))
+            .collect::<Vec<_>>();
         assert_eq!(expected, actual);
     }
+
+    #[cfg(feature = "which")]
+    #[test]
+    fn test_which_editor_not_found() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback("--NOT-AN-EDITOR-- --wait")
+            .build();
+        let Err(Error::EditorNotFound { command, .. }) = finder.which_editor() else {
+            panic!("The editor should not be found");
+        };
+        assert_eq!("--NOT-AN-EDITOR--", command);
+    }
+
+    #[cfg(all(feature = "which", unix))]
+    #[rstest]
+    #[case::absolute("/bin/sh --flag", true, Some("/bin/sh"))]
+    #[case::absolute_missing("/--NOT-AN-EDITOR--", true, None)]
+    #[case::relative_allowed("./--NOT-AN-EDITOR--", true, None)]
+    fn test_which_editor_path(
+        #[case] editor: &str,
+        #[case] allow_relative: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(editor)
+            .relative_editor_paths(allow_relative)
+            .build();
+        let actual = finder.which_editor().ok().map(|(path, _)| path);
+        assert_eq!(expected.map(PathBuf::from), actual);
+    }
+
+    #[cfg(feature = "which")]
+    #[test]
+    fn test_which_editor_relative_forbidden() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback("./edit")
+            .relative_editor_paths(false)
+            .build();
+        assert!(matches!(
+            finder.which_editor(),
+            Err(Error::RelativeEditorPath(_))
+        ));
+    }
+
+    #[cfg(feature = "which")]
+    #[test]
+    fn test_builder_skip_missing_editors_fallback() {
+        let f = |key: &OsStr| (key == "EDITOR").then(|| OsString::from("--NOT-AN-EDITOR--"));
+        let finder = Finder::builder()
+            .skip_missing_editors(true)
+            .fallback(FALLBACK)
+            .build();
+        let actual = finder.resolve_inner(f);
+        assert_eq!(&EditorSource::Fallback, actual.source());
+    }
+
+    #[cfg(all(unix, feature = "which"))]
+    #[rstest]
+    #[case::login_shell(true, true)]
+    #[case::path_only(false, false)]
+    fn test_builder_login_shell(#[case] login_shell: bool, #[case] expected: bool) {
+        let env = [("EDITOR", "sh"), ("PATH", ""), ("SHELL", "/bin/sh")]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::builder()
+            .env_source(env)
+            .login_shell(login_shell)
+            .build();
+        assert_eq!(expected, finder.which_editor().is_ok());
+    }
+
+    #[cfg(all(unix, feature = "which"))]
+    #[test]
+    fn test_which_editor_canonical() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        let vim = dir.join("vim.basic");
+        create_executable(&vim);
+        std::os::unix::fs::symlink(&vim, dir.join("editor")).unwrap();
+        let env = [("EDITOR", "editor -n")]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::builder()
+            .env_source(env)
+            .search_dirs([&dir])
+            .build();
+        assert_eq!(dir.join("editor"), finder.which_editor_os().unwrap().0);
+        let (path, args) = finder.which_editor_canonical().unwrap();
+        assert_eq!(vim, path);
+        assert_eq!(vec!["-n"], args);
+    }
+
+    #[cfg(all(unix, feature = "which"))]
+    #[test]
+    fn test_builder_search_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join("my-editor");
+        create_executable(&editor);
+        let env = [("EDITOR", "my-editor"), ("PATH", "/usr/bin")]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::builder()
+            .env_source(env.clone())
+            .search_dirs(["/nonexistent:dir", dir.path().to_str().unwrap()])
+            .build();
+        assert_eq!(editor, finder.which_editor().unwrap().0);
+        let finder = Finder::builder().env_source(env).search_path("").build();
+        assert!(finder.which_editor().is_err());
+    }
+
+    #[cfg(all(unix, feature = "which"))]
+    #[test]
+    fn test_builder_search_path_login_shell() {
+        let env = [("EDITOR", "sh"), ("PATH", ""), ("SHELL", "sh")]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let builder = Finder::builder().env_source(env).login_shell(true);
+        assert!(builder.clone().build().which_editor().is_ok());
+        let finder = builder.search_path("").build();
+        assert!(finder.which_editor().is_err());
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::enabled(true, ("TERM_PROGRAM", "vscode"), "code --wait")]
+    #[case::git_handle(true, ("VSCODE_GIT_IPC_HANDLE", "/tmp/vscode-git.sock"), "code --wait")]
+    #[case::disabled(false, ("TERM_PROGRAM", "vscode"), FALLBACK)]
+    #[case::other_terminal(true, ("TERM_PROGRAM", "iTerm.app"), FALLBACK)]
+    fn test_builder_vscode_terminal(
+        #[case] vscode_terminal: bool,
+        #[case] var: (&str, &str),
+        #[case] expected: &str,
+    ) {
+        let bin = tempfile::tempdir().unwrap();
+        let code = bin.path().join("code");
+        create_executable(&code);
+        let env = [var, ("PATH", bin.path().to_str().unwrap())]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::builder()
+            .fallback(FALLBACK)
+            .vscode_terminal(vscode_terminal)
+            .env_source(env)
+            .build();
+        assert_eq!(expected, finder.resolve().name());
+    }
+
+    #[cfg(all(windows, feature = "which"))]
+    #[test]
+    fn test_default_fallbacks_windows() {
+        let env = [("ProgramFiles", r"C:\Program Files")]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let actual = finder.editor_command(OsStr::new(Finder::DEFAULT_FALLBACKS[0]));
+        assert_eq!(
+            Some(OsString::from(r"C:\Program Files\Notepad++\notepad++.exe")),
+            actual
+        );
+    }
+
+    #[cfg(all(windows, feature = "which"))]
+    #[test]
+    fn test_default_fallbacks_windows_installed() {
+        let program_files = tempfile::tempdir().unwrap();
+        let notepad_plus_plus = program_files.path().join(r"Notepad++\notepad++.exe");
+        std::fs::create_dir(notepad_plus_plus.parent().unwrap()).unwrap();
+        std::fs::write(&notepad_plus_plus, "").unwrap();
+        let env = [("ProgramFiles", program_files.path().as_os_str())]
+            .into_iter()
+            .collect::<EnvSnapshot>();
+        let finder = Finder::builder().sources([]).env_source(env).build();
+        assert_eq!(notepad_plus_plus.as_os_str(), finder.editor_name_os());
+        let (editor, args) = finder.which_editor_os().unwrap();
+        assert_eq!(notepad_plus_plus, editor);
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn test_default_fallback() {
+        let env = [("PATH", "")].into_iter().collect::<EnvSnapshot>();
+        let finder = Finder::builder().sources([]).env_source(env).build();
+        let expected = if cfg!(windows) { "notepad.exe" } else { "vi" };
+        assert_eq!(expected, finder.resolve().name());
+    }
+
+    #[test]
+    fn test_resolve_all_blank() {
+        let f = |_: &OsStr| Some(OsString::from(""));
+        let finder = Finder::with_fallback(FALLBACK);
+        let actual = finder.resolve_inner(f);
+        assert_eq!(&EditorSource::Fallback, actual.source());
+        assert_eq!(FALLBACK, actual.name());
+    }
+
+    #[cfg(feature = "git")]
+    #[rstest]
+    #[case::git_editor_defined(["GIT_EDITOR"], EditorSource::GitEnv)]
+    #[case::env_before_git(["GIT_EDITOR", "EDITOR"], EditorSource::StandardEnv("EDITOR".into()))]
+    fn test_resolve_git<Keys>(#[case] defined_keys: Keys, #[case] expected: EditorSource)
+    where
+        Keys: IntoIterator<Item = &'static str> + Copy,
+    {
+        let f = |key: &OsStr| {
+            defined_keys
+                .into_iter()
+                .any(|defined| key == defined)
+                .then_some(OsString::from("foo"))
+        };
+        let finder = Finder::builder().git(true).build();
+        let actual = finder.resolve_inner(f);
+        assert_eq!(&expected, actual.source());
+    }
+
+    #[rstest]
+    #[case::extra_first(Some("foo"), EditorSource::ExtraEnv("MY_EXTRA".into()))]
+    #[case::custom_before_standard(None, EditorSource::Custom("my config".into()))]
+    fn test_resolve_custom(#[case] extra: Option<&str>, #[case] expected: EditorSource) {
+        struct MyConfig;
+
+        impl Resolver for MyConfig {
+            fn resolve(&self) -> Option<OsString> {
+                Some(OsString::from("bar"))
+            }
+
+            fn name(&self) -> &str {
+                "my config"
+            }
+        }
+
+        let f = |key: &OsStr| match key.to_str() {
+            Some("MY_EXTRA") => extra.map(OsString::from),
+            Some("EDITOR") => Some(OsString::from("baz")),
+            _ => None,
+        };
+        let finder = Finder::builder()
+            .extra_environment_variables(["MY_EXTRA"])
+            .resolver(MyConfig)
+            .build();
+        let actual = finder.resolve_inner(f);
+        assert_eq!(&expected, actual.source());
+    }
+
+    #[rstest]
+    #[case::default_order(None, EditorSource::ExtraEnv("MY_EXTRA".into()))]
+    #[case::custom_first(
+        Some(vec![Source::Custom, Source::ExtraEnv]),
+        EditorSource::Custom("custom resolver".into())
+    )]
+    #[case::standard_only(Some(vec![Source::StandardEnv]), EditorSource::StandardEnv("EDITOR".into()))]
+    #[case::none(Some(vec![]), EditorSource::Fallback)]
+    #[case::sudo(Some(vec![Source::Sudo, Source::ExtraEnv]), EditorSource::SudoEnv)]
+    fn test_builder_sources(#[case] sources: Option<Vec<Source>>, #[case] expected: EditorSource) {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("MY_EXTRA") => Some(OsString::from("foo")),
+            Some("EDITOR") => Some(OsString::from("bar")),
+            Some("SUDO_EDITOR") => Some(OsString::from("qux")),
+            _ => None,
+        };
+        let builder = Finder::builder()
+            .extra_environment_variables(["MY_EXTRA"])
+            .resolver(|| Some(OsString::from("baz")));
+        let builder = match sources {
+            Some(sources) => builder.sources(sources),
+            None => builder,
+        };
+        let actual = builder.build().resolve_inner(f);
+        assert_eq!(&expected, actual.source());
+    }
+
+    #[test]
+    fn test_resolve_stops_at_first_editor() {
+        let finder = Finder::builder()
+            .extra_environment_variables(["FIRST", "SECOND"])
+            .resolver(|| Some(OsString::from("nano")))
+            .resolver(|| -> Option<OsString> { panic!("Should stop at the first resolver") })
+            .sources([Source::Custom, Source::ExtraEnv])
+            .build();
+        let actual = finder.resolve_inner(|key| {
+            assert_ne!("FIRST", key, "Should stop at the first source");
+            None
+        });
+        assert_eq!("nano", actual.name());
+        let finder = Finder::builder()
+            .extra_environment_variables(["FIRST", "SECOND"])
+            .sources([Source::ExtraEnv])
+            .build();
+        let actual = finder.resolve_inner(|key| {
+            assert_ne!("SECOND", key, "Should stop at the first variable");
+            Some(OsString::from("vim"))
+        });
+        assert_eq!("vim", actual.name());
+    }
+
+    #[test]
+    fn test_builder_sudo_editor() {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("SUDO_EDITOR") => Some(OsString::from("sudo-editor")),
+            Some("VISUAL") => Some(OsString::from("visual")),
+            _ => None,
+        };
+        let finder = Finder::builder()
+            .extra_environment_variables(["VISUAL"])
+            .sudo_editor(true)
+            .build();
+        let actual = finder.resolve_inner(f);
+        assert_eq!("sudo-editor", actual.name());
+        assert_eq!(&EditorSource::SudoEnv, actual.source());
+    }
 }
@@ -0,0 +1,110 @@
+//! Utilities for opening native Windows editors from MSYS2, Cygwin, and Git Bash.
+use super::Finder;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// The DLLs that MSYS2 and Cygwin programs are linked with, which are installed next to
+/// the programs.
+const RUNTIME_DLLS: &[&str] = &["msys-2.0.dll", "cygwin1.dll"];
+
+/// Translates the MSYS-style path `file`, like `/c/Users/user/notes.txt`, into a Windows
+/// path, if `editor` is a native Windows program.
+///
+/// `/cygdrive/c/...` paths are always translated. Other paths, like `/c/...` and
+/// `/tmp/...`, are only translated when running in MSYS2 or Git Bash, where `$MSYSTEM`
+/// is set, because they're valid Windows paths on the current drive. Returns `None` if
+/// the path doesn't need to be translated, or can't be.
+pub(super) fn windows_path(finder: &Finder, editor: &Path, file: &Path) -> Option<PathBuf> {
+    let file_str = file.to_str().filter(|file| file.starts_with('/'))?;
+    if is_posix_program(editor) {
+        return None;
+    }
+    let msys = finder
+        .var_os(OsStr::new("MSYSTEM"))
+        .is_some_and(|msystem| !msystem.is_empty());
+    drive_path(file_str, msys)
+        .map(PathBuf::from)
+        .or_else(|| msys.then(|| cygpath(file)).flatten())
+}
+
+/// Translates a path on a drive, like `/c/Users` or `/cygdrive/c/Users`, following
+/// `cygpath`'s rules. `/c/Users` is only translated if `msys` is `true`.
+fn drive_path(file: &str, msys: bool) -> Option<String> {
+    let rest = match file.strip_prefix("/cygdrive/") {
+        Some(rest) => rest,
+        None if msys => file.strip_prefix('/')?,
+        None => return None,
+    };
+    let (drive, rest) = rest.split_once('/').unwrap_or((rest, ""));
+    let [letter] = drive.as_bytes() else {
+        return None;
+    };
+    if !letter.is_ascii_alphabetic() {
+        return None;
+    }
+    let letter = char::from(letter.to_ascii_uppercase());
+    Some(format!(r"{letter}:\{}", rest.replace('/', r"\")))
+}
+
+/// Translates `file` with `cygpath -w`, for paths in the MSYS2 or Cygwin installation,
+/// like `/tmp`.
+fn cygpath(file: &Path) -> Option<PathBuf> {
+    let output = Command::new("cygpath")
+        .arg("-w")
+        .arg(file)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = String::from_utf8(output.stdout).ok()?;
+    let path = path.trim();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Checks if `editor` is an MSYS2 or Cygwin program, which understands MSYS-style paths
+/// itself.
+fn is_posix_program(editor: &Path) -> bool {
+    editor
+        .parent()
+        .is_some_and(|dir| RUNTIME_DLLS.iter().any(|dll| dir.join(dll).is_file()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::msys_drive("/c/Users/user/notes.txt", true, Some(r"C:\Users\user\notes.txt"))]
+    #[case::msys_root("/d", true, Some(r"D:\"))]
+    #[case::cygdrive("/cygdrive/c/Users/notes.txt", false, Some(r"C:\Users\notes.txt"))]
+    #[case::not_msys("/c/Users/user/notes.txt", false, None)]
+    #[case::not_drive("/tmp/notes.txt", true, None)]
+    #[case::relative("c/notes.txt", true, None)]
+    fn test_drive_path(#[case] file: &str, #[case] msys: bool, #[case] expected: Option<&str>) {
+        assert_eq!(expected.map(String::from), drive_path(file, msys));
+    }
+
+    #[test]
+    fn test_windows_path_posix_program() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("msys-2.0.dll"), "").unwrap();
+        let env = [("MSYSTEM", "MINGW64")]
+            .into_iter()
+            .collect::<crate::EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let file = Path::new("/c/notes.txt");
+        assert_eq!(
+            None,
+            windows_path(&finder, &dir.path().join("vim.exe"), file)
+        );
+        assert_eq!(
+            Some(PathBuf::from(r"C:\notes.txt")),
+            windows_path(&finder, Path::new(r"C:\Windows\notepad.exe"), file)
+        );
+    }
+}
@@ -1,9 +1,9 @@
 //! Utilities for opening an editor.
 use super::Finder;
 use crate::Error;
-use std::ffi::OsString;
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus};
+use std::time::Duration;
 
 impl Finder {
     /// Opens an editor to edit `file`. Set `wait` to `true` to make this function wait
@@ -11,6 +11,9 @@ impl Finder {
     ///
     /// _When in doubt, you **should** set `wait` to `true`._
     ///
+    /// When waiting, known graphical editors that would return immediately are passed
+    /// the flag that makes them wait, like `code --wait`.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -23,14 +26,295 @@ impl Finder {
     where
         P: AsRef<Path>,
     {
-        let file = file.as_ref();
-        let (editor, args) = self.which_editor()?;
-        let mut args = args.into_iter().map(OsString::from).collect::<Vec<_>>();
-        args.push(file.into());
-        let mut child = Command::new(editor).args(args).spawn().map_err(Error::Io)?;
-        if wait {
-            child.wait().map_err(Error::Io)?;
-        }
+        self.open_options().wait(wait).open(file)?;
+        Ok(())
+    }
+
+    /// Opens an editor to edit `file` without blocking the async runtime. Set `wait` to
+    /// `true` to make the returned future wait until the editor is closed.
+    ///
+    /// See [`OpenOptions::open_async`](super::OpenOptions::open_async) for the runtimes
+    /// this supports, and [`Finder::open_editor`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// # async fn run() {
+    /// let finder = Finder::new();
+    /// finder
+    ///     .open_editor_async("config.toml", true)
+    ///     .await
+    ///     .expect("Should be able to edit the file");
+    /// # }
+    /// ```
+    #[cfg(any(feature = "async-process", feature = "tokio"))]
+    pub async fn open_editor_async<P>(&self, file: P, wait: bool) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.open_options().wait(wait).open_async(file).await?;
         Ok(())
     }
+
+    /// Opens an editor to edit all of `files` in a single invocation. Set `wait` to
+    /// `true` to make this function wait until the editor is closed before returning.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// finder
+    ///     .open_editor_many(["src/main.rs", "src/lib.rs"], true)
+    ///     .expect("Should be able to edit the files");
+    /// ```
+    pub fn open_editor_many<P, I>(&self, files: I, wait: bool) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = P>,
+    {
+        self.open_options().wait(wait).open_many(files)?;
+        Ok(())
+    }
+
+    /// Opens an editor to edit `path`, which can be a file or a directory, like a
+    /// project folder. Set `wait` to `true` to make this function wait until the editor
+    /// is closed before returning.
+    ///
+    /// See [`OpenOptions::open_path`](super::OpenOptions::open_path) for the editors
+    /// that can open directories.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// finder
+    ///     .open_path("my-project", false)
+    ///     .expect("Should be able to open the project");
+    /// ```
+    pub fn open_path<P>(&self, path: P, wait: bool) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.open_options().wait(wait).open_path(path)?;
+        Ok(())
+    }
+
+    /// Opens an editor without a file, like a scratch buffer. Set `wait` to `true` to
+    /// make this function wait until the editor is closed before returning.
+    ///
+    /// If the editor needs a file, an empty temporary file is created and its path is
+    /// returned. See [`OpenOptions::open_scratch`](super::OpenOptions::open_scratch) for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let scratch_file = finder.open_scratch(true).expect("Should open an editor");
+    /// if let Some(path) = scratch_file {
+    ///     let message = std::fs::read_to_string(&path).expect("Should read the message");
+    ///     std::fs::remove_file(path).expect("Should delete the file");
+    ///     println!("{message}");
+    /// }
+    /// ```
+    pub fn open_scratch(&self, wait: bool) -> Result<Option<PathBuf>, Error> {
+        self.open_options().wait(wait).open_scratch()
+    }
+
+    /// Opens an editor to view or edit `content`. Set `wait` to `true` to make this
+    /// function wait until the editor is closed before returning.
+    ///
+    /// The content is passed through stdin to editors that can read it, like `vim -`.
+    /// Otherwise, it's written to a temporary file and its path is returned. See
+    /// [`OpenOptions::open_stdin`](super::OpenOptions::open_stdin) for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let temp_file = finder
+    ///     .open_stdin("Generated output", true)
+    ///     .expect("Should open an editor");
+    /// if let Some(path) = temp_file {
+    ///     std::fs::remove_file(path).expect("Should delete the file");
+    /// }
+    /// ```
+    pub fn open_stdin<C>(&self, content: C, wait: bool) -> Result<Option<PathBuf>, Error>
+    where
+        C: AsRef<[u8]>,
+    {
+        self.open_options().wait(wait).open_stdin(content)
+    }
+
+    /// Opens an editor to edit `file`, waits until the editor is closed, and returns the
+    /// editor's exit status.
+    ///
+    /// This is useful if a non-zero exit status should cancel an operation, like how git
+    /// aborts a commit when the editor exits with an error (e.g. `:cq` in vim).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let status = finder
+    ///     .open_editor_status("COMMIT_EDITMSG")
+    ///     .expect("Should be able to edit the file");
+    /// if !status.success() {
+    ///     eprintln!("Aborting");
+    /// }
+    /// ```
+    pub fn open_editor_status<P>(&self, file: P) -> Result<ExitStatus, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let status = self.open_options().open(file)?;
+        Ok(status.expect("The editor should have been waited for"))
+    }
+
+    /// Opens an editor to edit `file`, waits until the editor is closed, and returns
+    /// whether the file's content changed.
+    ///
+    /// See [`OpenOptions::open_changed`](super::OpenOptions::open_changed) for more
+    /// information.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let changed = finder
+    ///     .open_editor_changed("COMMIT_EDITMSG")
+    ///     .expect("Should be able to edit the file");
+    /// if !changed {
+    ///     eprintln!("Aborting");
+    /// }
+    /// ```
+    pub fn open_editor_changed<P>(&self, file: P) -> Result<bool, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.open_options().open_changed(file)
+    }
+
+    /// Opens an editor to edit `file`, and waits up to `timeout` for the editor to be
+    /// closed before terminating it. Returns the editor's exit status, or
+    /// [`Error::TimedOut`] if it was terminated.
+    ///
+    /// See [`OpenOptions::timeout`](super::OpenOptions::timeout) for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::{Error, Finder};
+    /// use std::time::Duration;
+    ///
+    /// let finder = Finder::new();
+    /// match finder.open_editor_timeout("config.toml", Duration::from_secs(600)) {
+    ///     Ok(_) => println!("Done editing"),
+    ///     Err(Error::TimedOut) => eprintln!("Gave up waiting for the editor"),
+    ///     Err(e) => eprintln!("{e}"),
+    /// }
+    /// ```
+    pub fn open_editor_timeout<P>(&self, file: P, timeout: Duration) -> Result<ExitStatus, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let status = self.open_options().timeout(timeout).open(file)?;
+        Ok(status.expect("The editor should have been waited for"))
+    }
+
+    /// Opens an editor to edit `file` at a `line` and, optionally, a `column`, and waits
+    /// until the editor is closed. Lines and columns start at 1.
+    ///
+    /// The syntax for this differs between editors (e.g. `vim +10`,
+    /// `code --goto file:10:5`, `nano +10,5`). Editors with unknown syntax open the file
+    /// normally. Use [`Finder::open_options`] for more control.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// finder
+    ///     .open_editor_at("src/main.rs", 10, Some(5))
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    pub fn open_editor_at<P>(
+        &self,
+        file: P,
+        line: usize,
+        column: Option<usize>,
+    ) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let options = self.open_options().line(line);
+        let options = match column {
+            Some(column) => options.column(column),
+            None => options,
+        };
+        options.open(file)?;
+        Ok(())
+    }
+
+    /// Opens an editor to edit `file` without waiting, and returns the editor's process.
+    ///
+    /// This lets you wait for, kill, or check on the editor later. Known graphical
+    /// editors that would return immediately are passed the flag that makes them wait,
+    /// like `code --wait`, so that waiting on the process waits for the editor.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let mut child = finder.spawn_editor("config.toml").expect("Should open an editor");
+    /// // Do something else while the editor is open...
+    /// child.wait().expect("Should be able to wait for the editor");
+    /// ```
+    pub fn spawn_editor<P>(&self, file: P) -> Result<Child, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.open_options().spawn(file)
+    }
+
+    /// Builds the [`Command`] that would open an editor to edit `file`, without
+    /// running it.
+    ///
+    /// This is useful if you need to configure the command, like its stdio, environment
+    /// variables, or working directory, before spawning it. Like
+    /// [`Finder::spawn_editor`], this includes the flag that makes known graphical
+    /// editors wait.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let mut command = finder.command_for("config.toml").expect("Should find an editor");
+    /// command.current_dir("/path/to/project");
+    /// command.status().expect("Should be able to edit the file");
+    /// ```
+    pub fn command_for<P>(&self, file: P) -> Result<Command, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.open_options().command(file.as_ref())
+    }
 }
@@ -0,0 +1,2331 @@
+//! Contains the [`OpenOptions`] struct for configuring how an editor is opened.
+use super::wait::{AfterExit, Waiter};
+use super::{EditorSession, EditorSource, Finder, emacs, flatpak, host_editor, packaged, template};
+use crate::{EditorInfo, Error};
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Options for opening an editor.
+///
+/// Create one with [`Finder::open_options`].
+///
+/// # Placeholders
+///
+/// If the editor's arguments have a `{file}` placeholder, like
+/// `myedit --file={file}`, it's replaced with the file instead of passing the file
+/// after the arguments. An argument with `{file}` is repeated for each file, and is
+/// removed if there are no files. `{line}` and `{column}` are replaced with
+/// [`OpenOptions::line`] and [`OpenOptions::column`], or `1` if they aren't set.
+/// Placeholders aren't replaced if the editor is run with [`OpenOptions::shell`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use find_editor::Finder;
+///
+/// let finder = Finder::new();
+/// let status = finder
+///     .open_options()
+///     .current_dir("/path/to/project")
+///     .env("GIT_INDEX_FILE", ".git/index.tmp")
+///     .open("config.toml")
+///     .expect("Should be able to edit the file");
+/// ```
+pub struct OpenOptions<'a> {
+    /// The finder used to find the editor.
+    finder: &'a Finder,
+    /// If the editor should be waited for.
+    wait: bool,
+    /// If a known editor's wait flag should be added when waiting.
+    wait_flag: bool,
+    /// Extra arguments to pass to the editor before the file.
+    args: Vec<OsString>,
+    /// The editor's working directory.
+    current_dir: Option<PathBuf>,
+    /// Extra environment variables for the editor, or `None` for variables that are
+    /// removed.
+    envs: Vec<(OsString, Option<OsString>)>,
+    /// The editor's stdin.
+    stdin: Option<Stdio>,
+    /// The editor's stdout.
+    stdout: Option<Stdio>,
+    /// The editor's stderr.
+    stderr: Option<Stdio>,
+    /// If the editor should be opened in read-only mode, if it has one.
+    read_only: bool,
+    /// The line to open the file at.
+    line: Option<usize>,
+    /// The column to open the file at.
+    column: Option<usize>,
+    /// How to wait for the editor.
+    waiter: Waiter,
+    /// The process group to run the editor in.
+    process_group: ProcessGroup,
+    /// When to launch terminal editors in a new terminal.
+    terminal_mode: TerminalMode,
+    /// The terminal emulator command to launch terminal editors with.
+    terminal_emulator: Option<Vec<OsString>>,
+    /// If the editor is run through the user's shell.
+    shell: bool,
+    /// The macOS app to open the file with instead of the fallback editor.
+    mac_fallback_app: Option<MacApp>,
+    /// What to do if the editor can't be found.
+    fallback: OpenFallback,
+    /// If the editor is run on the host when in a Flatpak sandbox.
+    flatpak_spawn: bool,
+    /// If Emacs is opened with a running Emacs server.
+    emacs_server: bool,
+    /// If files are sent to the editor hosting the terminal.
+    reuse_host_editor: bool,
+    /// If a known editor's reuse-window flag should be added.
+    reuse_window: bool,
+    /// If the extra arguments are passed after the files.
+    args_after_files: bool,
+    /// If known graphical editors get null stdio by default.
+    null_gui_stdio: bool,
+    /// Called with the editor's command before it's spawned.
+    before_spawn: Option<Box<BeforeSpawnHook>>,
+    /// The user and group IDs to run the editor as.
+    #[cfg(unix)]
+    user: Option<(u32, u32)>,
+}
+
+/// A hook that's called with the editor's command before it's spawned.
+type BeforeSpawnHook = dyn FnMut(&mut Command) + Send;
+
+/// When to launch a terminal editor in a new terminal window.
+///
+/// This is useful for graphical applications without a terminal, where a terminal
+/// editor like vim would otherwise run invisibly, or fail. Only known terminal editors
+/// (see [`EditorInfo::gui`]) are launched in a new terminal.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TerminalMode {
+    /// Never launch a new terminal.
+    #[default]
+    Never,
+    /// Launch a new terminal if neither stdin nor stdout is a terminal.
+    Auto,
+    /// Always launch a new terminal.
+    Always,
+}
+
+/// What to do if an editor can't be found when opening a file.
+///
+/// See [`OpenOptions::fallback`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpenFallback {
+    /// Fail with the error that prevented the editor from being found.
+    #[default]
+    Error,
+    /// Open the file with the system's default application for it, using `xdg-open`,
+    /// `open` on macOS, or `start` on Windows.
+    SystemDefault,
+}
+
+/// A macOS app to open files with using `open`, which asks LaunchServices to launch the
+/// app.
+///
+/// See [`OpenOptions::mac_fallback_app`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacApp {
+    /// The user's default text editor (`open -t`).
+    DefaultTextEditor,
+    /// TextEdit (`open -e`).
+    TextEdit,
+    /// The app with this name, like `"BBEdit"` (`open -a`).
+    Named(OsString),
+}
+
+/// The process group, or session, that an editor runs in.
+///
+/// Running a graphical editor in its own process group keeps signals aimed at your
+/// application, like Ctrl-C, from reaching the editor, and vice versa. Terminal editors
+/// should usually be left in your application's process group, because they need to
+/// read from the terminal.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProcessGroup {
+    /// Run the editor in your application's process group.
+    #[default]
+    Inherit,
+    /// Run the editor in a new process group. On Windows, this uses
+    /// `CREATE_NEW_PROCESS_GROUP`.
+    New,
+    /// Run the editor in a new session, without a controlling terminal (`setsid`). On
+    /// Windows, this uses `CREATE_NEW_CONSOLE`.
+    NewSession,
+}
+
+impl Finder {
+    /// Creates [`OpenOptions`] to configure how an editor is opened.
+    #[inline]
+    pub fn open_options(&self) -> OpenOptions<'_> {
+        OpenOptions::new(self)
+    }
+}
+
+impl<'a> OpenOptions<'a> {
+    /// Creates new [`OpenOptions`] that open an editor found by `finder`.
+    fn new(finder: &'a Finder) -> Self {
+        Self {
+            finder,
+            wait: true,
+            wait_flag: true,
+            args: Vec::new(),
+            current_dir: None,
+            envs: Vec::new(),
+            stdin: None,
+            stdout: None,
+            stderr: None,
+            read_only: false,
+            line: None,
+            column: None,
+            waiter: Waiter::default(),
+            process_group: ProcessGroup::default(),
+            terminal_mode: TerminalMode::default(),
+            terminal_emulator: None,
+            shell: false,
+            mac_fallback_app: None,
+            fallback: OpenFallback::default(),
+            flatpak_spawn: false,
+            emacs_server: false,
+            reuse_host_editor: false,
+            reuse_window: false,
+            args_after_files: false,
+            null_gui_stdio: true,
+            before_spawn: None,
+            #[cfg(unix)]
+            user: None,
+        }
+    }
+
+    /// Sets whether to wait until the editor is closed. This defaults to `true`.
+    ///
+    /// When waiting, known graphical editors that would return immediately are passed
+    /// the flag that makes them wait, like `code --wait`. See [`EditorInfo`].
+    pub fn wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Sets whether to add a known editor's wait flag when waiting. This defaults to
+    /// `true`.
+    ///
+    /// The flag is never added twice if the editor's arguments already include it.
+    pub fn wait_flag(mut self, wait_flag: bool) -> Self {
+        self.wait_flag = wait_flag;
+        self
+    }
+
+    /// Adds an argument to pass to the editor before the file, or after it with
+    /// [`OpenOptions::args_after_files`].
+    pub fn arg<S>(mut self, arg: S) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.args.push(arg.as_ref().into());
+        self
+    }
+
+    /// Adds arguments to pass to the editor before the file, or after it with
+    /// [`OpenOptions::args_after_files`].
+    pub fn args<S, I>(mut self, args: I) -> Self
+    where
+        S: AsRef<OsStr>,
+        I: IntoIterator<Item = S>,
+    {
+        self.args
+            .extend(args.into_iter().map(|arg| arg.as_ref().into()));
+        self
+    }
+
+    /// Sets the editor's working directory. The editor inherits this process's working
+    /// directory by default.
+    ///
+    /// Editors that look for project configuration in their working directory, like VS
+    /// Code workspaces and Vim project plugins, should be started in the project's
+    /// root. Relative files are opened from this directory, because the editor resolves
+    /// them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// Finder::new()
+    ///     .open_options()
+    ///     .current_dir("/path/to/project")
+    ///     .open("src/main.rs")
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    pub fn current_dir<P>(mut self, dir: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.current_dir = Some(dir.as_ref().into());
+        self
+    }
+
+    /// Sets an environment variable for the editor.
+    pub fn env<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.envs
+            .push((key.as_ref().into(), Some(value.as_ref().into())));
+        self
+    }
+
+    /// Sets environment variables for the editor.
+    pub fn envs<K, V, I>(mut self, vars: I) -> Self
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.envs.extend(
+            vars.into_iter()
+                .map(|(key, value)| (key.as_ref().into(), Some(value.as_ref().into()))),
+        );
+        self
+    }
+
+    /// Removes an environment variable from the editor's environment, like
+    /// `LD_PRELOAD`.
+    pub fn env_remove<K>(mut self, key: K) -> Self
+    where
+        K: AsRef<OsStr>,
+    {
+        self.envs.push((key.as_ref().into(), None));
+        self
+    }
+
+    /// Sets the editor's stdin. The editor inherits this process's stdin by default,
+    /// unless it's a graphical editor (see [`OpenOptions::null_gui_stdio`]).
+    pub fn stdin<T>(mut self, stdin: T) -> Self
+    where
+        T: Into<Stdio>,
+    {
+        self.stdin = Some(stdin.into());
+        self
+    }
+
+    /// Sets the editor's stdout. The editor inherits this process's stdout by default,
+    /// unless it's a graphical editor (see [`OpenOptions::null_gui_stdio`]).
+    pub fn stdout<T>(mut self, stdout: T) -> Self
+    where
+        T: Into<Stdio>,
+    {
+        self.stdout = Some(stdout.into());
+        self
+    }
+
+    /// Sets the editor's stderr. The editor inherits this process's stderr by default,
+    /// unless it's a graphical editor (see [`OpenOptions::null_gui_stdio`]).
+    pub fn stderr<T>(mut self, stderr: T) -> Self
+    where
+        T: Into<Stdio>,
+    {
+        self.stderr = Some(stderr.into());
+        self
+    }
+
+    /// Sets whether to open the editor in read-only mode.
+    ///
+    /// This uses the read-only flag of known editors, like `vim -R`, `nano -v`, and
+    /// `kak -ro`, which can keep log viewers and similar tools from accidentally
+    /// editing files. Editors without a read-only mode, like VS Code, are opened
+    /// normally. See [`EditorInfo::read_only_flag`].
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets the line to open the file at, starting at 1.
+    ///
+    /// This is only supported by editors with known syntax for it, like `vim +10`,
+    /// `code --goto file:10`, and `emacs +10`. Other editors open the file normally.
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the column to open the file at, starting at 1. This is ignored unless
+    /// [`OpenOptions::line`] is also set.
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets how long to wait for the editor before terminating it. When the editor is
+    /// terminated, opening fails with [`Error::TimedOut`].
+    ///
+    /// On Unix, the editor is sent `SIGTERM`, and is killed with `SIGKILL` if it
+    /// doesn't exit soon after. On Windows, the editor is killed with
+    /// `TerminateProcess`. This has no effect if not waiting for the editor, or with
+    /// [`OpenOptions::open_async`].
+    ///
+    /// This is useful in automated environments, where a forgotten editor window
+    /// would otherwise hang forever.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.waiter.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether a non-zero exit status is an error. This defaults to `false`.
+    ///
+    /// When `true`, an editor that exits unsuccessfully, like with `:cq` in vim or by
+    /// crashing, fails with [`Error::EditorExited`]. This has no effect if not waiting
+    /// for the editor.
+    pub fn fail_on_nonzero(mut self, fail_on_nonzero: bool) -> Self {
+        self.waiter.fail_on_nonzero = fail_on_nonzero;
+        self
+    }
+
+    /// Sets whether to capture the editor's stderr, so that an editor that fails with
+    /// [`OpenOptions::fail_on_nonzero`] fails with an [`Error::EditorExited`] that
+    /// includes the end of its stderr. This defaults to `false`.
+    ///
+    /// This helps explain failures like a wrapper script that can't find the editor.
+    /// The captured stderr isn't shown. This only has an effect when failing on a
+    /// non-zero exit status, if stderr wasn't set with [`OpenOptions::stderr`], and when
+    /// this waits for the editor, so not with [`OpenOptions::spawn`] or
+    /// [`OpenOptions::open_async`].
+    pub fn capture_stderr(mut self, capture_stderr: bool) -> Self {
+        self.waiter.capture_stderr = capture_stderr;
+        self
+    }
+
+    /// Sets whether to ignore `SIGINT` and `SIGQUIT` while waiting for the editor. This
+    /// defaults to `false`.
+    ///
+    /// Pressing Ctrl-C in a terminal editor sends `SIGINT` to your process too, which
+    /// would otherwise kill it while the editor is still running. Like git, this
+    /// ignores those signals until the editor exits, and then restores their previous
+    /// handlers. Signal handlers are shared by the whole process, so avoid this if
+    /// other threads change them.
+    ///
+    /// This only has an effect on Unix, when waiting for the editor, and not with
+    /// [`OpenOptions::open_async`].
+    pub fn ignore_signals(mut self, ignore_signals: bool) -> Self {
+        self.waiter.ignore_signals = ignore_signals;
+        self
+    }
+
+    /// Sets whether to restore the terminal's settings after the editor exits. This
+    /// defaults to `false`.
+    ///
+    /// The terminal's settings (termios) are saved before the editor is opened, and
+    /// restored after it exits, even if it crashed or was terminated. This prevents a
+    /// crashed terminal editor from leaving the terminal in raw mode. Whether the
+    /// terminal is showing the alternate screen can't be saved, so it isn't restored.
+    ///
+    /// This only has an effect on Unix, when waiting for the editor, and not with
+    /// [`OpenOptions::open_async`].
+    pub fn restore_terminal(mut self, restore_terminal: bool) -> Self {
+        self.waiter.restore_terminal = restore_terminal;
+        self
+    }
+
+    /// Sets the process group, or session, to run the editor in. This defaults to
+    /// [`ProcessGroup::Inherit`].
+    pub fn process_group(mut self, process_group: ProcessGroup) -> Self {
+        self.process_group = process_group;
+        self
+    }
+
+    /// Sets when to launch a terminal editor in a new terminal window. This defaults to
+    /// [`TerminalMode::Never`].
+    ///
+    /// The editor is launched with the [terminal
+    /// emulator](OpenOptions::terminal_emulator) if one is set. Otherwise, on Windows,
+    /// the editor is opened in a new console window, and on other platforms,
+    /// `x-terminal-emulator -e` is used if it's on `$PATH` and the `which` feature is
+    /// enabled.
+    ///
+    /// Some terminal emulators return before the editor is closed, so the editor can't
+    /// be waited for.
+    pub fn terminal_mode(mut self, terminal_mode: TerminalMode) -> Self {
+        self.terminal_mode = terminal_mode;
+        self
+    }
+
+    /// Sets the terminal emulator command that terminal editors are launched with, like
+    /// `["wt.exe"]` or `["x-terminal-emulator", "-e"]`. The editor and its arguments are
+    /// passed after this command. See [`OpenOptions::terminal_mode`].
+    pub fn terminal_emulator<S, I>(mut self, command: I) -> Self
+    where
+        S: Into<OsString>,
+        I: IntoIterator<Item = S>,
+    {
+        self.terminal_emulator = Some(command.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets whether to run the editor through the user's shell. This defaults to
+    /// `false`.
+    ///
+    /// Some editors can only be run by a shell, like aliases, shell functions, or
+    /// editors with variable assignments like `TERM=xterm vim`. When this is `true`,
+    /// the editor is run with `"$SHELL" -c '<editor> "$@"'`, or with `sh` if `$SHELL`
+    /// isn't set. On Windows, it's run with `cmd /C`, and the other arguments, like the
+    /// files, are escaped so that `cmd` doesn't interpret them. The editor isn't
+    /// searched for on `$PATH`, and isn't split, so the shell interprets it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// // Works with EDITOR="TERM=xterm vim"
+    /// Finder::new()
+    ///     .open_options()
+    ///     .shell(true)
+    ///     .open("notes.txt")
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    pub fn shell(mut self, shell: bool) -> Self {
+        self.shell = shell;
+        self
+    }
+
+    /// Sets the macOS app to open files with when no editor is configured, instead of
+    /// the fallback editor. This only has an effect on macOS.
+    ///
+    /// Graphical apps usually don't have a terminal to run `vi` in, so an app like
+    /// TextEdit is a friendlier fallback. The app is launched with `open`, and when
+    /// waiting, `open -W` waits until the app quits, which can be after the file's
+    /// window is closed. The file must exist, and [`OpenOptions::line`] and other
+    /// editor-specific options are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::{Finder, MacApp};
+    ///
+    /// Finder::new()
+    ///     .open_options()
+    ///     .mac_fallback_app(MacApp::DefaultTextEditor)
+    ///     .open("notes.txt")
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    pub fn mac_fallback_app(mut self, app: MacApp) -> Self {
+        self.mac_fallback_app = Some(app);
+        self
+    }
+
+    /// Sets what to do if the editor can't be found, like when it isn't on `$PATH`.
+    /// This defaults to [`OpenFallback::Error`].
+    ///
+    /// For graphical applications, opening the file with [`OpenFallback::SystemDefault`]
+    /// can be better than an error. The system's default application might not be an
+    /// editor, and waiting for it only works with `open` on macOS and `start` on
+    /// Windows, because `xdg-open` usually exits after launching it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::{Finder, OpenFallback};
+    ///
+    /// Finder::new()
+    ///     .open_options()
+    ///     .fallback(OpenFallback::SystemDefault)
+    ///     .open("notes.txt")
+    ///     .expect("Should be able to open the file");
+    /// ```
+    pub fn fallback(mut self, fallback: OpenFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Sets whether to open Emacs with an Emacs server. This defaults to `false`.
+    ///
+    /// When this is `true`, `emacs` is replaced with `emacsclient` if an Emacs server is
+    /// running, so that the file opens in the existing Emacs. `emacsclient` is passed
+    /// `-a ""` to start a server if one isn't running, unless an alternate editor was
+    /// chosen with `-a` or `$ALTERNATE_EDITOR`. When not waiting, `-n` is passed so
+    /// that `emacsclient` exits without waiting for the file to be closed.
+    pub fn emacs_server(mut self, emacs_server: bool) -> Self {
+        self.emacs_server = emacs_server;
+        self
+    }
+
+    /// Sets whether to open the file in an existing window of the editor, if it's a
+    /// known editor that supports it. This defaults to `false`.
+    ///
+    /// This adds flags like `code --reuse-window`, `zed --add`, and `gvim --remote`, so
+    /// that opening files repeatedly doesn't open a new window each time. With gVim,
+    /// `--remote-wait` is used when waiting.
+    pub fn reuse_window(mut self, reuse_window: bool) -> Self {
+        self.reuse_window = reuse_window;
+        self
+    }
+
+    /// Sets whether to pass the arguments added with [`OpenOptions::arg`] after the
+    /// files, for editors that need the file before their flags. This defaults to
+    /// `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// // Runs `<editor> notes.txt --syntax markdown`
+    /// Finder::new()
+    ///     .open_options()
+    ///     .args(["--syntax", "markdown"])
+    ///     .args_after_files(true)
+    ///     .open("notes.txt")
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    pub fn args_after_files(mut self, args_after_files: bool) -> Self {
+        self.args_after_files = args_after_files;
+        self
+    }
+
+    /// Sets whether known graphical editors get [`Stdio::null`] for the stdin, stdout,
+    /// and stderr that aren't set with [`OpenOptions::stdin`], [`OpenOptions::stdout`],
+    /// and [`OpenOptions::stderr`]. This defaults to `true`.
+    ///
+    /// Graphical editors don't need the terminal, and some of them print logs to it.
+    /// They also shouldn't hold on to the stdio of a daemon that launched them.
+    /// Terminal editors, and editors that aren't known, always inherit stdio.
+    pub fn null_gui_stdio(mut self, null_gui_stdio: bool) -> Self {
+        self.null_gui_stdio = null_gui_stdio;
+        self
+    }
+
+    /// Sets a hook that's called with the editor's [`Command`] right before it's
+    /// spawned, to log the command or change it, like adding environment variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// Finder::new()
+    ///     .open_options()
+    ///     .before_spawn(|command| {
+    ///         eprintln!("Running {command:?}");
+    ///         command.env("MY_TOOL_EDITING", "1");
+    ///     })
+    ///     .open("notes.txt")
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    pub fn before_spawn<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&mut Command) + Send + 'static,
+    {
+        self.before_spawn = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets a hook that's called with the editor's exit status after it exits, like to
+    /// record metrics.
+    ///
+    /// The hook is only called if the editor is waited for, and not if it's terminated
+    /// because of [`OpenOptions::timeout`] or [`OpenOptions::cancel_on`]. It's called
+    /// before a non-zero exit status fails with [`OpenOptions::fail_on_nonzero`].
+    pub fn after_exit<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&ExitStatus) + Send + 'static,
+    {
+        self.waiter.after_exit = Some(AfterExit(Arc::new(Mutex::new(hook))));
+        self
+    }
+
+    /// Sets whether to send the file to the editor that's hosting the terminal, instead
+    /// of opening a nested editor. This defaults to `false`.
+    ///
+    /// When this is `true`, running in Neovim's terminal (`$NVIM`), in Vim's terminal
+    /// with a server (`$VIM_TERMINAL` and `$VIM_SERVERNAME`), or in Emacs with a server
+    /// (`$INSIDE_EMACS`) opens the file in that editor. Neovim can't wait for a remote
+    /// file, so [`neovim-remote`](https://github.com/mhinz/neovim-remote) (`nvr`) must be
+    /// installed to wait. Otherwise, the editor is found normally.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// Finder::new()
+    ///     .open_options()
+    ///     .reuse_host_editor(true)
+    ///     .open("COMMIT_EDITMSG")
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    pub fn reuse_host_editor(mut self, reuse_host_editor: bool) -> Self {
+        self.reuse_host_editor = reuse_host_editor;
+        self
+    }
+
+    /// Sets whether to run the editor on the host when your application is running in a
+    /// Flatpak sandbox. This defaults to `false`, and has no effect outside of a
+    /// sandbox, which is detected with `/.flatpak-info`.
+    ///
+    /// The editor usually isn't installed in the sandbox, so it's run with
+    /// `flatpak-spawn --host`, which needs the `--talk-name=org.freedesktop.Flatpak`
+    /// permission. The editor isn't searched for on `$PATH`, because the host finds
+    /// it. The file must be visible to the host at the same path, like a file in a home
+    /// directory the sandbox can access, or a file exported with the document portal.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// Finder::new()
+    ///     .open_options()
+    ///     .flatpak_spawn(true)
+    ///     .open("/home/user/notes.txt")
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    pub fn flatpak_spawn(mut self, flatpak_spawn: bool) -> Self {
+        self.flatpak_spawn = flatpak_spawn;
+        self
+    }
+
+    /// Sets the user and group IDs to run the editor as. This is only available on
+    /// Unix.
+    ///
+    /// Before the editor runs, its supplementary groups are replaced with `gid`, and
+    /// its group and user IDs are set. This usually requires running as root, and is
+    /// useful to drop privileges before opening an editor, like `sudoedit` does.
+    #[cfg(unix)]
+    pub fn user(mut self, uid: u32, gid: u32) -> Self {
+        self.user = Some((uid, gid));
+        self
+    }
+
+    /// Sets a flag that cancels waiting for the editor when it's set to `true`, like from
+    /// another thread. When cancelled, the editor is terminated like with
+    /// [`OpenOptions::timeout`], and opening fails with [`Error::Cancelled`].
+    ///
+    /// This has no effect if not waiting for the editor, or with
+    /// [`OpenOptions::open_async`]. Instead, dropping the future returned by
+    /// [`OpenOptions::open_async`] kills the editor.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// let shutdown = Arc::new(AtomicBool::new(false));
+    /// let handle = std::thread::spawn({
+    ///     let shutdown = Arc::clone(&shutdown);
+    ///     move || Finder::new().open_options().cancel_on(shutdown).open("notes.txt")
+    /// });
+    /// shutdown.store(true, Ordering::Relaxed);
+    /// handle.join().unwrap().expect_err("Should be cancelled");
+    /// ```
+    pub fn cancel_on(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.waiter.cancel = Some(cancel);
+        self
+    }
+
+    /// Opens the editor to edit `file`.
+    ///
+    /// If waiting for the editor, this returns the editor's exit status.
+    pub fn open<P>(self, file: P) -> Result<Option<ExitStatus>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let waiter = self.waiter();
+        let command = self.command(file.as_ref())?;
+        let child = spawn_waited(command, waiter.as_ref())?;
+        Self::finish(child, waiter)
+    }
+
+    /// Opens the editor to edit `file` without blocking the async runtime.
+    ///
+    /// With the `async-process` feature, this works with any async runtime. Otherwise,
+    /// this must be called from a tokio runtime. If waiting for the editor, this returns
+    /// the editor's exit status.
+    ///
+    /// If waiting for the editor, dropping the returned future before it completes
+    /// kills the editor, so this can be cancelled like other futures.
+    #[cfg(any(feature = "async-process", feature = "tokio"))]
+    pub async fn open_async<P>(self, file: P) -> Result<Option<ExitStatus>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let waiter = self.waiter();
+        let command = self.command(file.as_ref())?;
+        // NOTE async-process is preferred when both features are enabled, because it
+        //      doesn't need a specific runtime.
+        #[cfg(feature = "async-process")]
+        {
+            trace_spawn(&command);
+            let line = command_line(&command);
+            let mut child = async_process::Command::from(command)
+                .kill_on_drop(waiter.is_some())
+                .spawn()
+                .map_err(|source| Error::Spawn {
+                    command: line,
+                    source,
+                })?;
+            let Some(waiter) = waiter else {
+                return Ok(None);
+            };
+            let status = child.status().await.map_err(Error::Io)?;
+            waiter.check(status).map(Some)
+        }
+        #[cfg(not(feature = "async-process"))]
+        {
+            trace_spawn(&command);
+            let line = command_line(&command);
+            let mut child = tokio::process::Command::from(command)
+                .kill_on_drop(waiter.is_some())
+                .spawn()
+                .map_err(|source| Error::Spawn {
+                    command: line,
+                    source,
+                })?;
+            let Some(waiter) = waiter else {
+                return Ok(None);
+            };
+            let status = child.wait().await.map_err(Error::Io)?;
+            waiter.check(status).map(Some)
+        }
+    }
+
+    /// Opens the editor to edit `file`, waits until the editor is closed, and returns
+    /// whether the file's content changed. This ignores [`OpenOptions::wait`].
+    ///
+    /// The content is compared with a hash, so saving the file without changing it
+    /// doesn't count as a change. A file that didn't exist and was created does. A
+    /// relative `file` is found in [`OpenOptions::current_dir`], like the editor finds
+    /// it.
+    pub fn open_changed<P>(self, file: P) -> Result<bool, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = file.as_ref();
+        let path = self.file_path(file);
+        let before = hash_file(&path).map_err(Error::Io)?;
+        self.wait(true).open(file)?;
+        let after = hash_file(&path).map_err(Error::Io)?;
+        Ok(before != after)
+    }
+
+    /// Gets the path of a `file` that's passed to the editor. Relative files are in
+    /// [`OpenOptions::current_dir`], if it's set.
+    pub(super) fn file_path(&self, file: &Path) -> PathBuf {
+        match &self.current_dir {
+            Some(dir) => dir.join(file),
+            None => file.to_path_buf(),
+        }
+    }
+
+    /// Opens the editor to edit all of `files` in a single invocation.
+    ///
+    /// [`OpenOptions::line`] and [`OpenOptions::column`] are ignored. If waiting for the
+    /// editor, this returns the editor's exit status.
+    pub fn open_many<P, I>(self, files: I) -> Result<Option<ExitStatus>, Error>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = P>,
+    {
+        let waiter = self.waiter();
+        let files = files
+            .into_iter()
+            .map(|file| user_file(file.as_ref()))
+            .collect();
+        let command = self.command_with(false, |_| Ok(files))?;
+        let child = spawn_waited(command, waiter.as_ref())?;
+        Self::finish(child, waiter)
+    }
+
+    /// Opens the editor to edit `path`, which can be a file or a directory, like a
+    /// project folder.
+    ///
+    /// Known editors that can't open directories, like Notepad, fail with
+    /// [`Error::DirectoryUnsupported`] instead of being opened. Editors like VS Code
+    /// and Sublime Text open the directory as a project, and Vim browses it with netrw.
+    /// Unknown editors are passed the directory. If `path` is a file, this is the same
+    /// as [`OpenOptions::open`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// Finder::new()
+    ///     .open_options()
+    ///     .wait(false)
+    ///     .open_path("my-project")
+    ///     .expect("Should be able to open the project");
+    /// ```
+    pub fn open_path<P>(self, path: P) -> Result<Option<ExitStatus>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return self.open(path);
+        }
+        let waiter = self.waiter();
+        let command = self.command_with(false, |editor| match EditorInfo::for_command(editor) {
+            Some(info) if !info.opens_directories => {
+                Err(Error::DirectoryUnsupported { editor: info.name })
+            }
+            _ => Ok(vec![user_file(path)]),
+        })?;
+        let child = spawn_waited(command, waiter.as_ref())?;
+        Self::finish(child, waiter)
+    }
+
+    /// Opens the editor without a file, like a scratch buffer.
+    ///
+    /// Some editors need a file, like graphical editors that can only wait until a
+    /// file is closed, or editors that aren't known. For these, an empty temporary file
+    /// is created and its path is returned. It's up to you to read and delete that file.
+    /// [`OpenOptions::line`] and [`OpenOptions::column`] are ignored.
+    pub fn open_scratch(self) -> Result<Option<PathBuf>, Error> {
+        let waiter = self.waiter();
+        let mut scratch_file = None;
+        let command = self.command_with(false, |editor| {
+            let opens_without_file =
+                EditorInfo::for_command(editor).is_some_and(|info| info.opens_without_file);
+            if opens_without_file {
+                return Ok(Vec::new());
+            }
+            let path = create_scratch_file().map_err(Error::Io)?;
+            scratch_file = Some(path.clone());
+            Ok(vec![path])
+        })?;
+        let child = spawn_waited(command, waiter.as_ref())?;
+        Self::finish(child, waiter)?;
+        Ok(scratch_file)
+    }
+
+    /// Opens the editor to view or edit `content`, like generated output, without
+    /// creating a file if possible.
+    ///
+    /// Editors that can read from stdin, like `vim -` and `code -`, are passed
+    /// `content` through stdin, which replaces [`OpenOptions::stdin`]. For other
+    /// editors, `content` is written to a temporary file, and its path is returned.
+    /// It's up to you to delete that file. [`OpenOptions::line`] and
+    /// [`OpenOptions::column`] are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let report = "Generated report\n";
+    /// let temp_file = Finder::new()
+    ///     .open_options()
+    ///     .open_stdin(report)
+    ///     .expect("Should open an editor");
+    /// if let Some(path) = temp_file {
+    ///     std::fs::remove_file(path).expect("Should delete the file");
+    /// }
+    /// ```
+    pub fn open_stdin<C>(self, content: C) -> Result<Option<PathBuf>, Error>
+    where
+        C: AsRef<[u8]>,
+    {
+        let content = content.as_ref();
+        let waiter = self.waiter();
+        let mut temp_file = None;
+        let mut command = self.command_with(false, |editor| {
+            if let Some(arg) = EditorInfo::for_command(editor).and_then(|info| info.stdin_arg) {
+                return Ok(vec![arg.into()]);
+            }
+            let path = create_scratch_file().map_err(Error::Io)?;
+            std::fs::write(&path, content).map_err(Error::Io)?;
+            temp_file = Some(path.clone());
+            Ok(vec![path])
+        })?;
+        if temp_file.is_none() {
+            command.stdin(Stdio::piped());
+        }
+        let mut child = spawn_waited(command, waiter.as_ref())?;
+        if let Some(mut stdin) = child.stdin.take() {
+            use std::io::Write;
+
+            // NOTE The content is written from another thread, so that an editor that
+            //      doesn't read all of it can't block this thread.
+            let content = content.to_vec();
+            std::thread::spawn(move || {
+                // NOTE The editor may exit without reading everything.
+                let _ = stdin.write_all(&content);
+            });
+        }
+        Self::finish(child, waiter)?;
+        Ok(temp_file)
+    }
+
+    /// Gets how to wait for the editor, or `None` if not waiting.
+    ///
+    /// This must be called before the editor is opened.
+    fn waiter(&self) -> Option<Waiter> {
+        self.wait.then(|| {
+            let mut waiter = self.waiter.clone();
+            waiter.capture_stderr &= waiter.fail_on_nonzero && self.stderr.is_none();
+            waiter.save_terminal()
+        })
+    }
+
+    /// Waits for the editor with `waiter`, if waiting.
+    fn finish(child: Child, waiter: Option<Waiter>) -> Result<Option<ExitStatus>, Error> {
+        waiter.map(|waiter| waiter.wait(child)).transpose()
+    }
+
+    /// Opens the editor to edit `file` without waiting, and returns the editor's
+    /// process.
+    ///
+    /// This doesn't wait, but if [`OpenOptions::wait`] is `true`, the editor's wait flag
+    /// is still added so that waiting on the process waits for the editor.
+    pub fn spawn<P>(self, file: P) -> Result<Child, Error>
+    where
+        P: AsRef<Path>,
+    {
+        spawn_command(self.command(file.as_ref())?)
+    }
+
+    /// Opens the editor to edit `file` without waiting, and returns an
+    /// [`EditorSession`] that kills the editor when dropped.
+    ///
+    /// Like [`OpenOptions::spawn`], the editor's wait flag is still added if
+    /// [`OpenOptions::wait`] is `true`.
+    pub fn spawn_session<P>(self, file: P) -> Result<EditorSession, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.spawn(file).map(EditorSession::new)
+    }
+
+    /// Builds the command to open the editor.
+    pub(super) fn command(self, file: &Path) -> Result<Command, Error> {
+        self.command_with(true, |_| Ok(vec![user_file(file)]))
+    }
+
+    /// Gets the editor's command and any arguments.
+    ///
+    /// With the `which` feature, the command is found on `$PATH`, unless it's run on
+    /// the `host` of a Flatpak sandbox. Otherwise, the platform finds it when the editor
+    /// is spawned, which can run an executable in the current directory on Windows.
+    fn editor(&self, host: bool) -> Result<(PathBuf, Vec<OsString>), Error> {
+        #[cfg(feature = "which")]
+        if !host {
+            return self.finder.which_editor_os();
+        }
+        #[cfg(not(feature = "which"))]
+        let _ = host;
+        let (editor, args) = self.finder.split_editor_name_os()?;
+        Ok((editor.into(), args))
+    }
+
+    /// Builds the command to open the editor, with the files created by `files` from the
+    /// editor's path. The file is opened at [`OpenOptions::line`] if `goto` is `true`.
+    fn command_with<F>(self, goto: bool, files: F) -> Result<Command, Error>
+    where
+        F: FnOnce(&Path) -> Result<Vec<PathBuf>, Error>,
+    {
+        // NOTE WebAssembly can't spawn processes, even where it has an environment.
+        if cfg!(target_family = "wasm") {
+            return Err(Error::UnsupportedPlatform);
+        }
+        let host = self.flatpak_spawn && flatpak::is_sandboxed();
+        let mut launcher = self
+            .mac_fallback_app
+            .as_ref()
+            .filter(|_| cfg!(target_os = "macos"))
+            .filter(|_| *self.finder.resolve().source() == EditorSource::Fallback)
+            .map(|app| open_app_invocation(app, self.wait));
+        let shell_editor = launcher
+            .is_none()
+            .then(|| self.shell.then(|| self.finder.editor_name_os()))
+            .flatten();
+        let mut system_default = false;
+        let (mut editor, mut editor_args) = match &shell_editor {
+            _ if launcher.is_some() => (PathBuf::from("open"), Vec::new()),
+            // NOTE The editor is only split to find out which editor it is. If the shell
+            //      syntax can't be split, it's treated as an unknown editor.
+            Some(name) => self
+                .finder
+                .split_editor_name_os()
+                .map(|(editor, args)| (editor.into(), args))
+                .unwrap_or_else(|_| (name.into(), Vec::new())),
+            None => {
+                let host_editor = self
+                    .reuse_host_editor
+                    .then(|| host_editor::find(self.finder, self.wait))
+                    .flatten();
+                match host_editor.map_or_else(|| self.editor(host), Ok) {
+                    Ok(editor) => editor,
+                    Err(_) if self.fallback == OpenFallback::SystemDefault => {
+                        system_default = true;
+                        let invocation = system_default_invocation(self.wait);
+                        let program = PathBuf::from(&invocation[0]);
+                        launcher = Some(invocation);
+                        (program, Vec::new())
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+        if self.emacs_server && launcher.is_none() && shell_editor.is_none() {
+            emacs::use_server(self.finder, &mut editor, &mut editor_args, self.wait);
+        }
+        // NOTE Packaged editors are identified by their app, not by flatpak or snap.
+        let identity =
+            packaged::command(&editor, &editor_args).map_or_else(|| editor.clone(), PathBuf::from);
+        // NOTE Commands like /usr/bin/editor can be links to a known editor.
+        let identity = match EditorInfo::for_command(&identity) {
+            None if identity.is_absolute() => std::fs::canonicalize(&identity).unwrap_or(identity),
+            _ => identity,
+        };
+        let info = EditorInfo::for_command(&identity);
+        let wait_flag = info
+            .and_then(|info| info.wait_flag)
+            .filter(|_| self.wait && self.wait_flag)
+            .filter(|flag| {
+                let present = editor_args.iter().any(|arg| arg == flag);
+                !present && !self.args.iter().any(|arg| arg == flag)
+            });
+        let read_only_args = self.read_only.then(|| read_only_args(&identity)).flatten();
+        let reuse_window_flag = info
+            .and_then(|info| info.reuse_window_flag)
+            .filter(|_| self.reuse_window)
+            // NOTE Vim's --remote doesn't wait, so it has its own flag for waiting.
+            .map(|flag| match flag {
+                "--remote" if self.wait => "--remote-wait",
+                flag => flag,
+            })
+            .filter(|flag| {
+                let present = editor_args.iter().any(|arg| arg == flag);
+                !present && !self.args.iter().any(|arg| arg == flag)
+            });
+        let files = files(&identity)?
+            .iter()
+            .map(|file| editor_file(self.finder, &identity, file))
+            .collect::<Vec<_>>();
+        let line = self.line.filter(|_| goto);
+        let templated = shell_editor.is_none() && template::has_placeholders(&editor_args);
+        // NOTE Files that start with - would be parsed as options. Known editors are
+        //      told where the options end, and the others get ./ before the file.
+        let end_of_options = !templated
+            && line.is_none()
+            && !self.args_after_files
+            && reuse_window_flag.is_none()
+            && info.is_some_and(|info| info.end_of_options)
+            && files.iter().any(|file| is_option_like(file));
+        let files = if end_of_options {
+            files
+        } else {
+            files.into_iter().map(option_safe_file).collect()
+        };
+        let (editor_args, mut file_args) = if templated {
+            template::substitute(&editor_args, files, line, self.column)
+        } else {
+            let file_args = match (line, files.as_slice()) {
+                (Some(line), [file]) => goto_args(&identity, file, line, self.column),
+                _ => files.into_iter().map(PathBuf::into_os_string).collect(),
+            };
+            (editor_args, file_args)
+        };
+        if end_of_options {
+            file_args.insert(0, OsString::from("--"));
+        }
+        let null_stdio = self.null_gui_stdio && info.is_some_and(|info| info.gui);
+        let launch_terminal =
+            info.is_some_and(|info| !info.gui) && self.terminal_mode.should_launch();
+        let emulator = launch_terminal
+            .then(|| {
+                self.terminal_emulator
+                    .filter(|emulator| !emulator.is_empty())
+                    .or_else(default_terminal_emulator)
+            })
+            .flatten();
+        let invocation = match (launcher, &shell_editor) {
+            (Some(invocation), _) => invocation,
+            (None, Some(name)) => shell_invocation(self.finder, name),
+            // NOTE Batch files, like code.cmd, are run through cmd by Command itself,
+            //      which also escapes their arguments.
+            (None, None) if cfg!(windows) => script_interpreter(&editor)
+                .into_iter()
+                .flatten()
+                .chain([editor.into_os_string()])
+                .collect(),
+            (None, None) => vec![editor.into_os_string()],
+        };
+        let host_invocation =
+            host.then(|| flatpak::host_invocation(&self.envs, self.current_dir.as_deref()));
+        let words = host_invocation
+            .iter()
+            .flatten()
+            .chain(emulator.iter().flatten())
+            .chain(&invocation)
+            .collect::<Vec<_>>();
+        let (program, program_args) = words.split_first().expect("A program should be present");
+        let mut command = Command::new(program);
+        command.args(program_args);
+        // NOTE cmd interprets characters like & in the arguments, even when they're
+        //      quoted, so they're escaped for it.
+        let through_cmd = cfg!(windows) && (shell_editor.is_some() || system_default);
+        // NOTE cmd doesn't follow the usual quoting rules, so the editor is passed
+        //      as-is for it to interpret.
+        #[cfg(windows)]
+        if let Some(name) = &shell_editor {
+            use std::os::windows::process::CommandExt;
+            command.raw_arg(name);
+        }
+        let editor_args = if shell_editor.is_some() {
+            Vec::new()
+        } else {
+            editor_args
+        };
+        let process_group = match emulator {
+            // NOTE On Windows, a new console window is opened instead.
+            None if launch_terminal && cfg!(windows) => ProcessGroup::NewSession,
+            _ => self.process_group,
+        };
+        let (args_before, args_after) = if self.args_after_files {
+            (Vec::new(), self.args)
+        } else {
+            (self.args, Vec::new())
+        };
+        let args = editor_args
+            .into_iter()
+            .chain(wait_flag.map(OsString::from))
+            .chain(args_before)
+            .chain(read_only_args.map(OsString::from))
+            // NOTE Vim treats every argument after --remote as a file.
+            .chain(reuse_window_flag.map(OsString::from))
+            .chain(file_args)
+            .chain(args_after);
+        if through_cmd {
+            add_cmd_args(&mut command, args)?;
+        } else {
+            command.args(args);
+        }
+        for (key, value) in self.envs {
+            match value {
+                Some(value) => command.env(key, value),
+                None => command.env_remove(key),
+            };
+        }
+        // NOTE The host's working directory was passed to flatpak-spawn, and might not
+        //      exist in the sandbox.
+        if let Some(dir) = self.current_dir.filter(|_| !host) {
+            command.current_dir(dir);
+        }
+        let stdio = |stdio: Option<Stdio>| stdio.or_else(|| null_stdio.then(Stdio::null));
+        if let Some(stdin) = stdio(self.stdin) {
+            command.stdin(stdin);
+        }
+        if let Some(stdout) = stdio(self.stdout) {
+            command.stdout(stdout);
+        }
+        if let Some(stderr) = stdio(self.stderr) {
+            command.stderr(stderr);
+        }
+        process_group.apply(&mut command);
+        #[cfg(unix)]
+        if let Some((uid, gid)) = self.user {
+            drop_privileges(&mut command, uid, gid);
+        }
+        if let Some(mut hook) = self.before_spawn {
+            hook(&mut command);
+        }
+        Ok(command)
+    }
+}
+
+/// Spawns the editor's `command`.
+fn spawn_command(mut command: Command) -> Result<Child, Error> {
+    trace_spawn(&command);
+    command.spawn().map_err(|source| Error::Spawn {
+        command: command_line(&command),
+        source,
+    })
+}
+
+/// Spawns the editor's `command`, piping its stderr if `waiter` captures it.
+fn spawn_waited(mut command: Command, waiter: Option<&Waiter>) -> Result<Child, Error> {
+    // NOTE The pipe is only read while waiting, so an editor that writes more than the
+    //      pipe can hold would block if it's piped otherwise.
+    if waiter.is_some_and(|waiter| waiter.capture_stderr) {
+        command.stderr(Stdio::piped());
+    }
+    spawn_command(command)
+}
+
+/// Gets the editor's `command` as a command line, for errors.
+fn command_line(command: &Command) -> String {
+    let words = std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|word| word.to_string_lossy())
+        .collect::<Vec<_>>();
+    shell_words::join(words)
+}
+
+/// Records that the editor's `command` is being spawned.
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing")),
+    allow(unused_variables)
+)]
+fn trace_spawn(command: &Command) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        program = ?command.get_program(),
+        args = ?command.get_args().collect::<Vec<_>>(),
+        "spawning editor"
+    );
+    #[cfg(feature = "log")]
+    log::info!(
+        "Spawning editor: {:?} {:?}",
+        command.get_program(),
+        command.get_args().collect::<Vec<_>>()
+    );
+}
+
+impl TerminalMode {
+    /// Checks if a new terminal should be launched.
+    fn should_launch(self) -> bool {
+        use std::io::IsTerminal;
+
+        match self {
+            Self::Never => false,
+            Self::Auto => !io::stdin().is_terminal() && !io::stdout().is_terminal(),
+            Self::Always => true,
+        }
+    }
+}
+
+impl ProcessGroup {
+    /// Configures `command` to run in this process group.
+    #[cfg(unix)]
+    fn apply(self, command: &mut Command) {
+        use std::os::unix::process::CommandExt;
+
+        match self {
+            Self::Inherit => {}
+            Self::New => {
+                command.process_group(0);
+            }
+            Self::NewSession => {
+                // SAFETY: setsid is async-signal-safe.
+                unsafe {
+                    command.pre_exec(|| {
+                        if libc::setsid() == -1 {
+                            return Err(io::Error::last_os_error());
+                        }
+                        Ok(())
+                    });
+                }
+            }
+        }
+    }
+
+    /// Configures `command` to run in this process group.
+    #[cfg(windows)]
+    fn apply(self, command: &mut Command) {
+        use std::os::windows::process::CommandExt;
+
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+
+        match self {
+            Self::Inherit => {}
+            Self::New => {
+                command.creation_flags(CREATE_NEW_PROCESS_GROUP);
+            }
+            Self::NewSession => {
+                command.creation_flags(CREATE_NEW_CONSOLE);
+            }
+        }
+    }
+
+    /// Configures `command` to run in this process group. Process groups aren't
+    /// supported on this platform.
+    #[cfg(not(any(unix, windows)))]
+    fn apply(self, _command: &mut Command) {}
+}
+
+/// Makes `command` run as the user `uid` in the group `gid`.
+#[cfg(unix)]
+fn drop_privileges(command: &mut Command, uid: u32, gid: u32) {
+    use std::os::unix::process::CommandExt;
+
+    // NOTE The groups must be set before the user, because an unprivileged user
+    //      can't change groups.
+    // SAFETY: setgroups, setgid, and setuid are async-signal-safe.
+    unsafe {
+        command.pre_exec(move || {
+            let groups = [gid as libc::gid_t];
+            // NOTE The length's type differs between platforms.
+            if libc::setgroups(groups.len() as _, groups.as_ptr()) != 0
+                || libc::setgid(gid as libc::gid_t) != 0
+                || libc::setuid(uid as libc::uid_t) != 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Gets the words that run `editor` through the user's shell. The editor's arguments
+/// are passed after these words.
+#[cfg(not(windows))]
+fn shell_invocation(finder: &Finder, editor: &OsStr) -> Vec<OsString> {
+    let shell = finder
+        .var_os(OsStr::new("SHELL"))
+        .filter(|shell| !super::is_blank(shell))
+        .unwrap_or_else(|| OsString::from("sh"));
+    let mut script = editor.to_owned();
+    script.push(r#" "$@""#);
+    // NOTE The word after the script is $0, so the arguments after it are "$@".
+    vec![shell, OsString::from("-c"), script, OsString::from("sh")]
+}
+
+/// Gets the words that run an editor through `cmd`. The editor is passed after these
+/// words without quoting, followed by the editor's arguments.
+#[cfg(windows)]
+fn shell_invocation(_finder: &Finder, _editor: &OsStr) -> Vec<OsString> {
+    vec![OsString::from("cmd"), OsString::from("/C")]
+}
+
+/// Adds `args` to a `command` that runs through `cmd`, escaping them so that `cmd`
+/// passes them to the program as-is.
+#[cfg(windows)]
+fn add_cmd_args<I>(command: &mut Command, args: I) -> Result<(), Error>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    use std::os::windows::process::CommandExt;
+
+    for arg in args {
+        let arg = arg.into_string().map_err(|arg| {
+            let message = format!("{} can't be passed through cmd", arg.to_string_lossy());
+            Error::Io(io::Error::new(io::ErrorKind::InvalidInput, message))
+        })?;
+        command.raw_arg(cmd_escape(&arg));
+    }
+    Ok(())
+}
+
+/// Adds `args` to a `command`. Only Windows has `cmd`.
+#[cfg(not(windows))]
+fn add_cmd_args<I>(command: &mut Command, args: I) -> Result<(), Error>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    command.args(args);
+    Ok(())
+}
+
+/// Escapes `arg` for `cmd`. The argument is quoted like Windows programs parse their
+/// arguments, and then every character that `cmd` interprets, including the quotes, is
+/// escaped with `^`.
+#[cfg(any(windows, test))]
+fn cmd_escape(arg: &str) -> String {
+    let mut quoted = String::new();
+    if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '"']) {
+        quoted.push_str(arg);
+    } else {
+        quoted.push('"');
+        let mut backslashes = 0;
+        for c in arg.chars() {
+            match c {
+                '\\' => backslashes += 1,
+                '"' => {
+                    quoted.extend(std::iter::repeat_n('\\', backslashes * 2 + 1));
+                    backslashes = 0;
+                }
+                _ => {
+                    quoted.extend(std::iter::repeat_n('\\', backslashes));
+                    backslashes = 0;
+                }
+            }
+            if c != '\\' {
+                quoted.push(c);
+            }
+        }
+        // NOTE Backslashes before the closing quote would escape it.
+        quoted.extend(std::iter::repeat_n('\\', backslashes * 2));
+        quoted.push('"');
+    }
+    let mut escaped = String::with_capacity(quoted.len());
+    for c in quoted.chars() {
+        if matches!(c, '(' | ')' | '%' | '!' | '^' | '"' | '<' | '>' | '&' | '|') {
+            escaped.push('^');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Gets the words that open files with a macOS `app`. The files are passed after these
+/// words.
+fn open_app_invocation(app: &MacApp, wait: bool) -> Vec<OsString> {
+    let mut words = vec![OsString::from("open")];
+    if wait {
+        words.push(OsString::from("-W"));
+    }
+    match app {
+        MacApp::DefaultTextEditor => words.push(OsString::from("-t")),
+        MacApp::TextEdit => words.push(OsString::from("-e")),
+        MacApp::Named(name) => words.extend([OsString::from("-a"), name.clone()]),
+    }
+    words
+}
+
+/// Gets the path to pass to the editor for a `file` that the user chose. `-` reads from
+/// stdin in many editors, so it's passed as `./-`.
+fn user_file(file: &Path) -> PathBuf {
+    if file.as_os_str() == "-" {
+        Path::new(".").join(file)
+    } else {
+        file.to_path_buf()
+    }
+}
+
+/// Checks if `file` starts with `-`, so that it could be parsed as an option. `-` itself
+/// is the argument that reads from stdin.
+fn is_option_like(file: &Path) -> bool {
+    let file = file.as_os_str();
+    file != "-" && file.as_encoded_bytes().starts_with(b"-")
+}
+
+/// Adds `./` before `file` if it starts with `-`, so that it isn't parsed as an option.
+fn option_safe_file(file: PathBuf) -> PathBuf {
+    if is_option_like(&file) {
+        Path::new(".").join(file)
+    } else {
+        file
+    }
+}
+
+/// Gets the path that `editor` should open `file` with. Windows editors can't open
+/// paths like `/home/user/notes.txt` in WSL, or `/c/Users/user/notes.txt` from Git
+/// Bash, so they're translated.
+#[cfg_attr(not(windows), allow(unused_variables))]
+fn editor_file(finder: &Finder, editor: &Path, file: &Path) -> PathBuf {
+    #[cfg(target_os = "linux")]
+    if let Some(file) = super::wsl::windows_path(editor, file) {
+        return file;
+    }
+    #[cfg(windows)]
+    if let Some(file) = super::msys::windows_path(finder, editor, file) {
+        return file;
+    }
+    file.to_path_buf()
+}
+
+/// Gets the words that open files with the system's default application. The files are
+/// passed after these words.
+fn system_default_invocation(wait: bool) -> Vec<OsString> {
+    let words: &[&str] = if cfg!(windows) {
+        // NOTE start treats its first quoted argument as the window's title, so an
+        //      empty title is passed before the file. The file is escaped for cmd.
+        if wait {
+            &["cmd", "/C", "start", "/WAIT", ""]
+        } else {
+            &["cmd", "/C", "start", ""]
+        }
+    } else if cfg!(target_os = "macos") {
+        if wait { &["open", "-W"] } else { &["open"] }
+    } else {
+        &["xdg-open"]
+    };
+    words.iter().map(OsString::from).collect()
+}
+
+/// Gets the command that runs a script editor that can't be run directly on Windows,
+/// like a PowerShell wrapper `code.ps1`. The script is passed after this command.
+fn script_interpreter(editor: &Path) -> Option<Vec<OsString>> {
+    let extension = editor.extension()?;
+    if !extension.eq_ignore_ascii_case("ps1") {
+        return None;
+    }
+    // NOTE The machine's execution policy applies, so the script might not be
+    //      allowed to run.
+    let command = ["powershell", "-NoProfile", "-File"];
+    Some(command.map(OsString::from).into())
+}
+
+/// Finds the platform's default terminal emulator, if it has one.
+#[cfg(feature = "which")]
+fn default_terminal_emulator() -> Option<Vec<OsString>> {
+    if cfg!(windows) {
+        return None;
+    }
+    let emulator = which::which("x-terminal-emulator").ok()?;
+    Some(vec![emulator.into(), OsString::from("-e")])
+}
+
+/// Finds the platform's default terminal emulator, if it has one. Without the `which`
+/// feature, `x-terminal-emulator` can't be found.
+#[cfg(not(feature = "which"))]
+fn default_terminal_emulator() -> Option<Vec<OsString>> {
+    None
+}
+
+/// Hashes a file's content, or returns `None` if the file doesn't exist.
+pub(super) fn hash_file(path: &Path) -> io::Result<Option<u64>> {
+    use std::hash::{DefaultHasher, Hasher};
+    use std::io::Read;
+
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut hasher = DefaultHasher::new();
+    let mut buf = [0; 8192];
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buf[..read]);
+    }
+    Ok(Some(hasher.finish()))
+}
+
+/// Creates a new, empty file in the temporary directory.
+fn create_scratch_file() -> io::Result<PathBuf> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Makes names unique within this process.
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos())
+        .unwrap_or_default();
+    loop {
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let name = format!("find-editor-{}-{nanos}-{count}.txt", process::id());
+        let path = env::temp_dir().join(name);
+        // NOTE create_new fails instead of following an existing file or symlink.
+        match File::create_new(&path) {
+            Ok(_) => return Ok(path),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Gets the argument that opens an editor in read-only mode, if it has one.
+fn read_only_args(editor: &Path) -> Option<&'static str> {
+    EditorInfo::for_command(editor).and_then(|info| info.read_only_flag)
+}
+
+/// Gets the arguments that open `file` at a line and column, or just `file` if the
+/// editor's syntax for it isn't known.
+fn goto_args(editor: &Path, file: &Path, line: usize, column: Option<usize>) -> Vec<OsString> {
+    match EditorInfo::for_command(editor).and_then(|info| info.goto) {
+        Some(goto) => goto.args(file, line, column),
+        None => vec![file.into()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use crate::finder::test_utils::{create_executable, create_script, fallback_finder};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::vim("/usr/bin/vim", &["-R"])]
+    #[case::nano("/usr/bin/nano", &["-v"])]
+    #[case::kakoune("kak", &["-ro"])]
+    #[case::view("/usr/bin/view", &["-R"])]
+    #[case::unknown("/usr/bin/code", &[])]
+    fn test_read_only_args(#[case] editor: &str, #[case] expected: &[&str]) {
+        let actual = read_only_args(Path::new(editor));
+        assert_eq!(expected, actual.as_slice());
+    }
+
+    #[rstest]
+    #[case::powershell(
+        r"C:\bin\code.ps1",
+        Some(&["powershell", "-NoProfile", "-File"][..])
+    )]
+    #[case::powershell_uppercase(
+        r"C:\bin\code.PS1",
+        Some(&["powershell", "-NoProfile", "-File"][..])
+    )]
+    #[case::batch(r"C:\bin\code.cmd", None)]
+    #[case::executable("/usr/bin/vim", None)]
+    fn test_script_interpreter(#[case] editor: &str, #[case] expected: Option<&[&str]>) {
+        let actual = script_interpreter(Path::new(editor));
+        let expected = expected.map(|command| command.iter().map(OsString::from).collect());
+        assert_eq!(expected, actual);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_open_batch_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let code = dir.path().join("code.cmd");
+        let script = "@echo off\r\necho %*> \"%~dp0output.txt\"\r\n";
+        std::fs::write(&code, script).unwrap();
+        let finder = Finder::builder().sources([]).fallback(&code).build();
+        let command = finder
+            .open_options()
+            .command(Path::new("a&calc.txt"))
+            .unwrap();
+        assert_eq!(code, command.get_program());
+        finder.open_options().open("a&calc.txt").unwrap();
+        let output = std::fs::read_to_string(dir.path().join("output.txt")).unwrap();
+        assert!(output.contains("a&calc.txt"), "{output:?}");
+    }
+
+    #[rstest]
+    #[case::vim_line("vim", None, &["+10", "f.txt"])]
+    #[case::vim_column("vim", Some(5), &["+call cursor(10, 5)", "f.txt"])]
+    #[case::nano("nano", Some(5), &["+10,5", "f.txt"])]
+    #[case::emacs("emacs", Some(5), &["+10:5", "f.txt"])]
+    #[case::micro("micro", Some(5), &["+10:5", "f.txt"])]
+    #[case::code("code", Some(5), &["--goto", "f.txt:10:5"])]
+    #[case::subl("subl", None, &["f.txt:10"])]
+    #[case::unknown("notepad", Some(5), &["f.txt"])]
+    fn test_goto_args(
+        #[case] editor: &str,
+        #[case] column: Option<usize>,
+        #[case] expected: &[&str],
+    ) {
+        let actual = goto_args(Path::new(editor), Path::new("f.txt"), 10, column);
+        assert_eq!(expected, actual.as_slice());
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::no_wait(false, &[], &["file.txt"])]
+    #[case::wait(true, &[], &["-w", "file.txt"])]
+    #[case::already_present(true, &["-w"], &["-w", "file.txt"])]
+    fn test_command_wait_flag(
+        #[case] wait: bool,
+        #[case] args: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        let subl = dir.path().join("subl");
+        let finder = fallback_finder(&subl);
+        let command = finder
+            .open_options()
+            .wait(wait)
+            .args(args)
+            .command(Path::new("file.txt"))
+            .unwrap();
+        let actual = command.get_args().collect::<Vec<_>>();
+        assert_eq!(expected, actual.as_slice());
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::code("code", false, &["--reuse-window", "file.txt"])]
+    #[case::gvim("gvim", false, &["--remote", "file.txt"])]
+    #[case::gvim_wait("gvim", true, &["-f", "--remote-wait", "file.txt"])]
+    #[case::unknown("my-editor", false, &["file.txt"])]
+    fn test_command_reuse_window(
+        #[case] editor: &str,
+        #[case] wait: bool,
+        #[case] expected: &[&str],
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join(editor);
+        let finder = fallback_finder(&editor);
+        let command = finder
+            .open_options()
+            .wait(wait)
+            .reuse_window(true)
+            .command(Path::new("file.txt"))
+            .unwrap();
+        let actual = command.get_args().collect::<Vec<_>>();
+        assert_eq!(expected, actual.as_slice());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_linked_editor() {
+        let dir = tempfile::tempdir().unwrap();
+        let vim = dir.path().join("vim.basic");
+        create_executable(&vim);
+        let editor = dir.path().join("editor");
+        std::os::unix::fs::symlink(&vim, &editor).unwrap();
+        let finder = Finder::builder().sources([]).fallback(&editor).build();
+        let command = finder
+            .open_options()
+            .line(3)
+            .command(Path::new("file.txt"))
+            .unwrap();
+        assert_eq!(editor, command.get_program());
+        let actual = command.get_args().collect::<Vec<_>>();
+        assert_eq!(["+3", "file.txt"], actual.as_slice());
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::end_of_options("vim", "-notes.txt", None, &["--", "-notes.txt"])]
+    #[case::goto("vim", "-notes.txt", Some(3), &["+3", "./-notes.txt"])]
+    #[case::unknown("my-editor", "-notes.txt", None, &["./-notes.txt"])]
+    #[case::stdin_name("vim", "-", None, &["./-"])]
+    #[case::not_option_like("vim", "notes-.txt", None, &["notes-.txt"])]
+    fn test_command_option_like_file(
+        #[case] editor: &str,
+        #[case] file: &str,
+        #[case] line: Option<usize>,
+        #[case] expected: &[&str],
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join(editor);
+        let finder = fallback_finder(&editor);
+        let mut options = finder.open_options();
+        if let Some(line) = line {
+            options = options.line(line);
+        }
+        let command = options.command(Path::new(file)).unwrap();
+        let actual = command.get_args().collect::<Vec<_>>();
+        assert_eq!(expected, actual.as_slice());
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::before(false, &["--cmd", "set nu", "-R", "--", "-notes.txt"])]
+    #[case::after(true, &["-R", "./-notes.txt", "--cmd", "set nu"])]
+    fn test_command_args_after_files(#[case] args_after_files: bool, #[case] expected: &[&str]) {
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join("vim");
+        let finder = fallback_finder(&editor);
+        let command = finder
+            .open_options()
+            .args(["--cmd", "set nu"])
+            .read_only(true)
+            .args_after_files(args_after_files)
+            .command(Path::new("-notes.txt"))
+            .unwrap();
+        let actual = command.get_args().collect::<Vec<_>>();
+        assert_eq!(expected, actual.as_slice());
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::file_and_line("{editor} --file={file} --line={line}", Some(5), &["--file=file.txt", "--line=5"])]
+    #[case::default_line("{editor} {file}:{line}", None, &["file.txt:1"])]
+    #[case::line_only("{editor} +{line}", Some(5), &["+5", "file.txt"])]
+    fn test_command_placeholders(
+        #[case] editor_value: &str,
+        #[case] line: Option<usize>,
+        #[case] expected: &[&str],
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join("my-editor");
+        create_executable(&editor);
+        let editor_value = editor_value.replace("{editor}", editor.to_str().unwrap());
+        let finder = Finder::builder().sources([]).fallback(editor_value).build();
+        let mut options = finder.open_options();
+        if let Some(line) = line {
+            options = options.line(line);
+        }
+        let command = options.command(Path::new("file.txt")).unwrap();
+        let actual = command.get_args().collect::<Vec<_>>();
+        assert_eq!(expected, actual.as_slice());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_null_gui_stdio() {
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join("code");
+        let script =
+            "#!/bin/sh\nfor file; do :; done\nreadlink /proc/$$/fd/0 /proc/$$/fd/2 > \"$file\"\n";
+        create_script(&editor, script);
+        let stdin = dir.path().join("stdin.txt");
+        std::fs::write(&stdin, "").unwrap();
+        let file = dir.path().join("file.txt");
+        let finder = Finder::builder().sources([]).fallback(&editor).build();
+        finder
+            .open_options()
+            .stdin(File::open(&stdin).unwrap())
+            .open(&file)
+            .unwrap();
+        let expected = format!("{}\n/dev/null\n", stdin.display());
+        assert_eq!(expected, std::fs::read_to_string(&file).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_stderr() {
+        let editor = r#"sh -c 'echo "editor not found" >&2; exit 3'"#;
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let actual = finder
+            .open_options()
+            .fail_on_nonzero(true)
+            .capture_stderr(true)
+            .open("file.txt");
+        match actual {
+            Err(Error::EditorExited { status, stderr }) => {
+                assert_eq!(Some(3), status.code());
+                assert_eq!(Some("editor not found"), stderr.as_deref());
+            }
+            actual => panic!("Expected the editor to fail, got {actual:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_stderr_spawn() {
+        let finder = Finder::builder().sources([]).fallback("true").build();
+        let mut child = finder
+            .open_options()
+            .fail_on_nonzero(true)
+            .capture_stderr(true)
+            .spawn("file.txt")
+            .unwrap();
+        assert!(child.stderr.is_none());
+        child.wait().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_current_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'pwd > "$0"'"#)
+            .build();
+        finder
+            .open_options()
+            .current_dir(dir.path())
+            .open("pwd.txt")
+            .unwrap();
+        let pwd = std::fs::read_to_string(dir.path().join("pwd.txt")).unwrap();
+        let expected = dir.path().canonicalize().unwrap();
+        assert_eq!(expected, Path::new(pwd.trim_end()).canonicalize().unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_changed_current_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "notes").unwrap();
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'echo changed >> "$0"'"#)
+            .build();
+        let changed = finder
+            .open_options()
+            .current_dir(dir.path())
+            .open_changed("notes.txt")
+            .unwrap();
+        assert!(changed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_env_remove() {
+        let finder = Finder::builder().sources([]).fallback("true").build();
+        let command = finder
+            .open_options()
+            .env("GIT_INDEX_FILE", ".git/index.tmp")
+            .env("LD_PRELOAD", "libhook.so")
+            .env_remove("LD_PRELOAD")
+            .command(Path::new("file.txt"))
+            .unwrap();
+        let mut envs = command.get_envs().collect::<Vec<_>>();
+        envs.sort();
+        assert_eq!(
+            [
+                (
+                    OsStr::new("GIT_INDEX_FILE"),
+                    Some(OsStr::new(".git/index.tmp"))
+                ),
+                (OsStr::new("LD_PRELOAD"), None)
+            ],
+            envs.as_slice()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_error() {
+        let env = [("EDITOR", "vim"), ("SHELL", "/nonexistent/sh")]
+            .into_iter()
+            .collect::<crate::EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let actual = finder.open_options().shell(true).open("my notes.txt");
+        match actual {
+            Err(Error::Spawn { command, source }) => {
+                assert_eq!(
+                    r#"/nonexistent/sh -c 'vim "$@"' sh 'my notes.txt'"#,
+                    command
+                );
+                assert_eq!(io::ErrorKind::NotFound, source.kind());
+            }
+            actual => panic!("Expected the editor to fail to start, got {actual:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_before_spawn() {
+        let finder = Finder::builder().sources([]).fallback("true").build();
+        let command = finder
+            .open_options()
+            .before_spawn(|command| {
+                command.env("MY_TOOL_EDITING", "1");
+            })
+            .command(Path::new("file.txt"))
+            .unwrap();
+        let envs = command.get_envs().collect::<Vec<_>>();
+        assert!(envs.contains(&(OsStr::new("MY_TOOL_EDITING"), Some(OsStr::new("1")))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_after_exit() {
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let finder = Finder::builder().sources([]).fallback("false").build();
+        let actual = finder
+            .open_options()
+            .fail_on_nonzero(true)
+            .after_exit({
+                let statuses = Arc::clone(&statuses);
+                move |status| statuses.lock().unwrap().push(status.success())
+            })
+            .open("file.txt");
+        assert!(matches!(actual, Err(Error::EditorExited { .. })));
+        assert_eq!([false], statuses.lock().unwrap().as_slice());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_path_directory_unsupported() {
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join("notepad");
+        let finder = fallback_finder(&editor);
+        let actual = finder.open_options().open_path(dir.path());
+        assert!(matches!(
+            actual,
+            Err(Error::DirectoryUnsupported { editor: "Notepad" })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::unchanged("true", false)]
+    #[case::saved_without_changes(r#"sh -c 'cat "$0" > "$0.tmp" && mv "$0.tmp" "$0"'"#, false)]
+    #[case::changed(r#"sh -c 'echo changed >> "$0"'"#, true)]
+    fn test_open_changed(#[case] editor: &str, #[case] expected: bool) {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "content").unwrap();
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let actual = finder.open_options().open_changed(file.path()).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_args() {
+        let finder = Finder::builder().sources([]).fallback("sh -e").build();
+        let command = finder
+            .open_options()
+            .args(["-x", "-u"])
+            .command(Path::new("file.txt"))
+            .unwrap();
+        let args = command.get_args().collect::<Vec<_>>();
+        assert_eq!(["-e", "-x", "-u", "file.txt"], args.as_slice());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_shell() {
+        let env = [("SHELL", "/bin/bash"), ("EDITOR", "TERM=xterm vim -n")]
+            .into_iter()
+            .collect::<crate::EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let command = finder
+            .open_options()
+            .shell(true)
+            .command(Path::new("file.txt"))
+            .unwrap();
+        assert_eq!("/bin/bash", command.get_program());
+        let args = command.get_args().collect::<Vec<_>>();
+        assert_eq!(
+            ["-c", r#"TERM=xterm vim -n "$@""#, "sh", "file.txt"],
+            args.as_slice()
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_command_shell_cmd() {
+        let env = [("EDITOR", "notepad")]
+            .into_iter()
+            .collect::<crate::EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let command = finder
+            .open_options()
+            .shell(true)
+            .command(Path::new("a&calc.txt"))
+            .unwrap();
+        assert_eq!("cmd", command.get_program());
+        let args = command.get_args().collect::<Vec<_>>();
+        assert_eq!(["/C", "notepad", "a^&calc.txt"], args.as_slice());
+    }
+
+    #[cfg(all(windows, feature = "which"))]
+    #[test]
+    fn test_command_system_default_cmd() {
+        let env = [("EDITOR", "editor-that-does-not-exist"), ("PATH", "")]
+            .into_iter()
+            .collect::<crate::EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let command = finder
+            .open_options()
+            .fallback(OpenFallback::SystemDefault)
+            .command(Path::new("a&calc.txt"))
+            .unwrap();
+        assert_eq!("cmd", command.get_program());
+        let args = command.get_args().collect::<Vec<_>>();
+        assert_eq!(["/C", "start", "/WAIT", "", "a^&calc.txt"], args.as_slice());
+    }
+
+    #[rstest]
+    #[case::plain("notes.txt", "notes.txt")]
+    #[case::ampersand("a&calc.txt", "a^&calc.txt")]
+    #[case::space("my notes.txt", r#"^"my notes.txt^""#)]
+    #[case::variable("%PATH%.txt", "^%PATH^%.txt")]
+    #[case::quote(r#"a"|b"#, r#"^"a\^"^|b^""#)]
+    #[case::trailing_backslash(r"C:\my dir\", r#"^"C:\my dir\\^""#)]
+    #[case::empty("", r#"^"^""#)]
+    fn test_cmd_escape(#[case] arg: &str, #[case] expected: &str) {
+        assert_eq!(expected, cmd_escape(arg));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("output.txt");
+        let vim = dir.path().join("vim");
+        let script = format!(
+            "#!/bin/sh\n[ \"$1\" = - ] && cat > '{}'\n",
+            output.display()
+        );
+        create_script(&vim, &script);
+        let finder = Finder::builder().sources([]).fallback(&vim).build();
+        let temp_file = finder.open_stdin("content\n", true).unwrap();
+        assert_eq!(None, temp_file);
+        assert_eq!("content\n", std::fs::read_to_string(output).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_stdin_temp_file() {
+        let finder = Finder::builder().sources([]).fallback("true").build();
+        let temp_file = finder.open_stdin("content\n", true).unwrap().unwrap();
+        let actual = std::fs::read_to_string(&temp_file).unwrap();
+        std::fs::remove_file(temp_file).unwrap();
+        assert_eq!("content\n", actual);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_shell() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let env = [("SHELL", "sh")]
+            .into_iter()
+            .collect::<crate::EnvSnapshot>();
+        let finder = Finder::builder()
+            .env_source(env)
+            .fallback(r#"VALUE=changed; echo "$VALUE" >"#)
+            .build();
+        finder.open_options().shell(true).open(file.path()).unwrap();
+        let actual = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!("changed\n", actual);
+    }
+
+    #[cfg(all(unix, feature = "async-process"))]
+    #[test]
+    fn test_open_async() {
+        let finder = Finder::builder().sources([]).fallback("false").build();
+        let status = futures_lite::future::block_on(finder.open_options().open_async("f.txt"));
+        assert!(!status.unwrap().unwrap().success());
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::exited("true", true)]
+    #[case::timed_out("sh -c 'sleep 10'", false)]
+    fn test_open_timeout(#[case] editor: &str, #[case] expected: bool) {
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let start = std::time::Instant::now();
+        let actual = finder
+            .open_options()
+            .timeout(Duration::from_millis(200))
+            .open("f.txt");
+        assert!(start.elapsed() < Duration::from_secs(5));
+        match actual {
+            Ok(_) => assert!(expected),
+            Err(Error::TimedOut) => assert!(!expected),
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_cancelled() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback("sh -c 'sleep 10'")
+            .build();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let actual = std::thread::scope(|scope| {
+            let handle = scope.spawn(|| {
+                finder
+                    .open_options()
+                    .cancel_on(Arc::clone(&cancel))
+                    .open("f.txt")
+            });
+            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+            handle.join().unwrap()
+        });
+        assert!(matches!(actual, Err(Error::Cancelled)));
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::success("true", true, true)]
+    #[case::ignored("false", false, true)]
+    #[case::failure("false", true, false)]
+    fn test_fail_on_nonzero(
+        #[case] editor: &str,
+        #[case] fail_on_nonzero: bool,
+        #[case] expected: bool,
+    ) {
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let actual = finder
+            .open_options()
+            .fail_on_nonzero(fail_on_nonzero)
+            .open("f.txt");
+        match actual {
+            Ok(_) => assert!(expected),
+            Err(Error::EditorExited { status, .. }) => assert!(!expected && !status.success()),
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::inherit(ProcessGroup::Inherit, false)]
+    #[case::new(ProcessGroup::New, true)]
+    #[case::new_session(ProcessGroup::NewSession, true)]
+    fn test_process_group(#[case] process_group: ProcessGroup, #[case] expected: bool) {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback(r#"sh -c 'test "$(ps -o pgid= -p $$)" -eq $$'"#)
+            .build();
+        let status = finder
+            .open_options()
+            .process_group(process_group)
+            .open("f.txt")
+            .unwrap()
+            .unwrap();
+        assert_eq!(expected, status.success());
+    }
+
+    #[rstest]
+    #[case::default_text_editor(MacApp::DefaultTextEditor, true, &["open", "-W", "-t"])]
+    #[case::text_edit(MacApp::TextEdit, false, &["open", "-e"])]
+    #[case::named(MacApp::Named("BBEdit".into()), true, &["open", "-W", "-a", "BBEdit"])]
+    fn test_open_app_invocation(
+        #[case] app: MacApp,
+        #[case] wait: bool,
+        #[case] expected: &[&str],
+    ) {
+        assert_eq!(expected, open_app_invocation(&app, wait));
+    }
+
+    #[cfg(all(unix, feature = "which"))]
+    #[rstest]
+    #[case::found("/bin/sh", false)]
+    #[case::missing("editor-that-does-not-exist", true)]
+    fn test_command_system_default(#[case] editor: &str, #[case] expected: bool) {
+        let env = [("EDITOR", editor), ("PATH", "")]
+            .into_iter()
+            .collect::<crate::EnvSnapshot>();
+        let finder = Finder::with_env_source(env);
+        let command = finder
+            .open_options()
+            .fallback(OpenFallback::SystemDefault)
+            .command(Path::new("file.txt"))
+            .unwrap();
+        let program = command.get_program();
+        let expected_program = system_default_invocation(true).remove(0);
+        assert_eq!(expected, program == expected_program);
+        assert_eq!(Some(OsStr::new("file.txt")), command.get_args().last());
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::fallback(None)]
+    #[case::configured(Some("/bin/sh"))]
+    fn test_command_mac_fallback_app(#[case] editor: Option<&str>) {
+        let env = editor
+            .map(|editor| ("EDITOR", editor))
+            .into_iter()
+            .collect::<crate::EnvSnapshot>();
+        let finder = Finder::builder()
+            .fallback("/bin/sh")
+            .env_source(env)
+            .build();
+        let command = finder
+            .open_options()
+            .mac_fallback_app(MacApp::TextEdit)
+            .command(Path::new("file.txt"))
+            .unwrap();
+        let uses_app = cfg!(target_os = "macos") && editor.is_none();
+        assert_eq!(uses_app, command.get_program() == "open");
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::never(TerminalMode::Never, "vim", &["file.txt"])]
+    #[case::always(TerminalMode::Always, "xterm", &["-e", "vim", "file.txt"])]
+    fn test_command_terminal_mode(
+        #[case] terminal_mode: TerminalMode,
+        #[case] expected_program: &str,
+        #[case] expected_args: &[&str],
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        let vim = dir.path().join("vim");
+        let finder = fallback_finder(&vim);
+        let command = finder
+            .open_options()
+            .terminal_mode(terminal_mode)
+            .terminal_emulator(["xterm", "-e"])
+            .command(Path::new("file.txt"))
+            .unwrap();
+        let program = Path::new(command.get_program()).file_name().unwrap();
+        let args = command
+            .get_args()
+            .map(|arg| Path::new(arg).file_name().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(expected_program, program);
+        assert_eq!(expected_args, args.as_slice());
+    }
+}
@@ -0,0 +1,126 @@
+//! Serializes [`OsString`]s as strings, so they're readable in config files.
+//!
+//! Use these with `#[serde(with = "...")]`. Values that aren't valid unicode can't be
+//! serialized.
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::ffi::{OsStr, OsString};
+
+/// Gets `value` as a string, or fails if it isn't valid unicode.
+fn to_str<E>(value: &OsStr) -> Result<&str, E>
+where
+    E: serde::ser::Error,
+{
+    value
+        .to_str()
+        .ok_or_else(|| E::custom(format!("{value:?} is not valid unicode")))
+}
+
+/// Serializes an [`OsString`] as a string.
+pub(super) mod os_string {
+    use super::*;
+
+    pub fn serialize<S>(value: &OsStr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        to_str(value)?.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<OsString, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(OsString::from)
+    }
+}
+
+/// Serializes an optional [`OsString`] as an optional string.
+pub(super) mod option {
+    use super::*;
+
+    pub fn serialize<S>(value: &Option<OsString>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value
+            .as_deref()
+            .map(to_str)
+            .transpose()?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<OsString>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer).map(|value| value.map(OsString::from))
+    }
+}
+
+/// Serializes a list of [`OsString`]s as a list of strings.
+pub(super) mod vec {
+    use super::*;
+
+    pub fn serialize<S>(values: &[OsString], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values = values
+            .iter()
+            .map(|value| to_str(value))
+            .collect::<Result<Vec<_>, S::Error>>()?;
+        values.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<OsString>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values = Vec::<String>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(OsString::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Values {
+        #[serde(with = "os_string")]
+        one: OsString,
+        #[serde(with = "option")]
+        maybe: Option<OsString>,
+        #[serde(with = "vec")]
+        many: Vec<OsString>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values = Values {
+            one: "vim".into(),
+            maybe: None,
+            many: vec!["nano".into(), "code --wait".into()],
+        };
+        let json = serde_json::to_string(&values).unwrap();
+        assert_eq!(
+            r#"{"one":"vim","maybe":null,"many":["nano","code --wait"]}"#,
+            json
+        );
+        assert_eq!(values, serde_json::from_str(&json).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_not_unicode() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let values = Values {
+            one: OsString::from_vec(vec![0xff]),
+            maybe: None,
+            many: Vec::new(),
+        };
+        assert!(serde_json::to_string(&values).is_err());
+    }
+}
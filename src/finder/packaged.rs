@@ -0,0 +1,154 @@
+//! Utilities for editors that are packaged as Flatpak apps or Snaps.
+#[cfg(feature = "which")]
+use super::Finder;
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+#[cfg(feature = "which")]
+use std::path::PathBuf;
+
+/// The Flatpak app IDs of well-known editors, and the editors' commands.
+const FLATPAK_APPS: &[(&str, &str)] = &[
+    ("com.visualstudio.code", "code"),
+    ("com.vscodium.codium", "codium"),
+    ("com.sublimetext.three", "subl"),
+    ("dev.zed.Zed", "zed"),
+    ("org.gnome.gedit", "gedit"),
+    ("org.vim.Vim", "gvim"),
+    ("io.neovim.nvim", "nvim"),
+    ("org.gnu.emacs", "emacs"),
+];
+
+/// Where Snaps' commands are installed.
+#[cfg(feature = "which")]
+const SNAP_BIN: &str = "/snap/bin";
+
+/// Finds an editor that isn't on `$PATH`, because it's packaged as a Flatpak app or a
+/// Snap. Returns the path to `flatpak` or `snap`, and the arguments that run the
+/// editor.
+///
+/// `command` can be a Flatpak app ID, like `org.gnome.TextEditor`, or the command of
+/// an editor that's installed as a Flatpak app or Snap, like `codium`.
+#[cfg(feature = "which")]
+pub(super) fn find(finder: &Finder, command: &OsStr) -> Option<(PathBuf, Vec<OsString>)> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let name = command.to_str()?;
+    let app_id = if is_app_id(name) {
+        Some(name)
+    } else {
+        FLATPAK_APPS
+            .iter()
+            .find(|(_, app_command)| *app_command == name)
+            .map(|(app_id, _)| *app_id)
+    };
+    let home = finder.var_os(OsStr::new("HOME"));
+    let flatpak = app_id
+        .filter(|app_id| is_flatpak_installed(app_id, home.as_deref()))
+        .and_then(|app_id| {
+            let path = finder.which(OsStr::new("flatpak")).ok()?;
+            Some((path, vec![OsString::from("run"), OsString::from(app_id)]))
+        });
+    flatpak.or_else(|| {
+        if name.contains('/') || !Path::new(SNAP_BIN).join(name).is_file() {
+            return None;
+        }
+        let path = finder.which(OsStr::new("snap")).ok()?;
+        Some((path, vec![OsString::from("run"), OsString::from(name)]))
+    })
+}
+
+/// Gets the command of an editor that's run by `flatpak run` or `snap run`, so that it
+/// can be identified, or `None` if `editor` isn't `flatpak` or `snap`.
+///
+/// A Flatpak app's command is only known for well-known editors. Otherwise, its app ID
+/// is returned.
+#[cfg(feature = "open")]
+pub(super) fn command<'a>(editor: &Path, args: &'a [OsString]) -> Option<&'a OsStr> {
+    let runner = editor.file_stem()?;
+    let [run, name, ..] = args else {
+        return None;
+    };
+    if run != "run" {
+        return None;
+    }
+    if runner == "snap" {
+        return Some(name);
+    }
+    if runner != "flatpak" {
+        return None;
+    }
+    let command = FLATPAK_APPS
+        .iter()
+        .find(|(app_id, _)| name == app_id)
+        .map_or(name.as_os_str(), |(_, command)| OsStr::new(command));
+    Some(command)
+}
+
+/// Checks if `name` looks like a Flatpak app ID, like `org.gnome.TextEditor`.
+#[cfg(feature = "which")]
+fn is_app_id(name: &str) -> bool {
+    let parts = name.split('.').collect::<Vec<_>>();
+    parts.len() >= 3
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+/// Checks if the Flatpak app `app_id` is installed for the system, or for the user
+/// with the `home` directory.
+#[cfg(feature = "which")]
+fn is_flatpak_installed(app_id: &str, home: Option<&OsStr>) -> bool {
+    let system = Path::new("/var/lib/flatpak/app");
+    let user = home.map(|home| Path::new(home).join(".local/share/flatpak/app"));
+    [Some(system), user.as_deref()]
+        .into_iter()
+        .flatten()
+        .any(|dir| dir.join(app_id).is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[cfg(feature = "which")]
+    #[rstest]
+    #[case::app_id("org.gnome.TextEditor", true)]
+    #[case::dashes("io.github.some-user.Editor_2", true)]
+    #[case::command("codium", false)]
+    #[case::extension("notepad.exe", false)]
+    #[case::empty_part("org..Editor", false)]
+    #[case::path("./org.gnome.TextEditor", false)]
+    fn test_is_app_id(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(expected, is_app_id(name));
+    }
+
+    #[cfg(feature = "which")]
+    #[test]
+    fn test_is_flatpak_installed() {
+        let home = tempfile::tempdir().unwrap();
+        let app_dir = home
+            .path()
+            .join(".local/share/flatpak/app/org.example.Editor");
+        std::fs::create_dir_all(app_dir).unwrap();
+        let home = Some(home.path().as_os_str());
+        assert!(is_flatpak_installed("org.example.Editor", home));
+        assert!(!is_flatpak_installed("org.example.Missing", home));
+    }
+
+    #[cfg(feature = "open")]
+    #[rstest]
+    #[case::known_flatpak("/usr/bin/flatpak", &["run", "com.vscodium.codium"], Some("codium"))]
+    #[case::flatpak("flatpak", &["run", "org.gnome.TextEditor"], Some("org.gnome.TextEditor"))]
+    #[case::snap("/usr/bin/snap", &["run", "code", "--wait"], Some("code"))]
+    #[case::not_run("/usr/bin/flatpak", &["list"], None)]
+    #[case::other("/usr/bin/vim", &["run", "code"], None)]
+    fn test_command(#[case] editor: &str, #[case] args: &[&str], #[case] expected: Option<&str>) {
+        let args = args.iter().map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(expected.map(OsStr::new), command(Path::new(editor), &args));
+    }
+}
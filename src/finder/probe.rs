@@ -0,0 +1,209 @@
+//! Contains the [`InstalledEditor`] struct for editors found by probing the system.
+use super::{Finder, is_executable};
+use crate::EditorInfo;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// A well-known editor that is installed.
+///
+/// Find them with [`Finder::probe_installed`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledEditor {
+    /// The editor's metadata.
+    pub info: &'static EditorInfo,
+    /// The command that runs the editor, like `nvim`.
+    pub command: &'static str,
+    /// The path to the editor's executable.
+    pub path: PathBuf,
+}
+
+/// Where editors are installed outside of `$PATH`, by command.
+#[cfg(target_os = "macos")]
+const INSTALL_LOCATIONS: &[(&str, &[&str])] = &[
+    (
+        "code",
+        &["/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code"],
+    ),
+    (
+        "subl",
+        &["/Applications/Sublime Text.app/Contents/SharedSupport/bin/subl"],
+    ),
+    ("mvim", &["/Applications/MacVim.app/Contents/bin/mvim"]),
+    ("zed", &["/Applications/Zed.app/Contents/MacOS/cli"]),
+    (
+        "mate",
+        &["/Applications/TextMate.app/Contents/SharedSupport/Support/bin/mate"],
+    ),
+    (
+        "idea",
+        &["/Applications/IntelliJ IDEA.app/Contents/MacOS/idea"],
+    ),
+    (
+        "pycharm",
+        &["/Applications/PyCharm.app/Contents/MacOS/pycharm"],
+    ),
+    ("clion", &["/Applications/CLion.app/Contents/MacOS/clion"]),
+    (
+        "goland",
+        &["/Applications/GoLand.app/Contents/MacOS/goland"],
+    ),
+    (
+        "webstorm",
+        &["/Applications/WebStorm.app/Contents/MacOS/webstorm"],
+    ),
+    (
+        "rustrover",
+        &["/Applications/RustRover.app/Contents/MacOS/rustrover"],
+    ),
+];
+
+/// Where editors are installed outside of `$PATH`, by command.
+#[cfg(windows)]
+const INSTALL_LOCATIONS: &[(&str, &[&str])] = &[
+    (
+        "code",
+        &[
+            r"C:\Program Files\Microsoft VS Code\bin\code.cmd",
+            r"C:\Program Files (x86)\Microsoft VS Code\bin\code.cmd",
+        ],
+    ),
+    (
+        "subl",
+        &[
+            r"C:\Program Files\Sublime Text\subl.exe",
+            r"C:\Program Files\Sublime Text 3\subl.exe",
+        ],
+    ),
+    (
+        "notepad++",
+        &[
+            r"C:\Program Files\Notepad++\notepad++.exe",
+            r"C:\Program Files (x86)\Notepad++\notepad++.exe",
+        ],
+    ),
+    ("gvim", &[r"C:\Program Files\Vim\vim91\gvim.exe"]),
+    ("vim", &[r"C:\Program Files\Vim\vim91\vim.exe"]),
+];
+
+/// Where editors are installed outside of `$PATH`, by command.
+#[cfg(not(any(target_os = "macos", windows)))]
+const INSTALL_LOCATIONS: &[(&str, &[&str])] = &[];
+
+impl Finder {
+    /// Finds the well-known editors that are installed, in the order of
+    /// [`EditorInfo::all`].
+    ///
+    /// Each command of each editor is searched for on `$PATH`, then in standard
+    /// install locations, like `/Applications` on macOS and `C:\Program Files` on
+    /// Windows, and then in the scripts directory of the JetBrains Toolbox App. This
+    /// can help settings dialogs and first-run wizards offer a choice of editors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use find_editor::Finder;
+    ///
+    /// for installed in Finder::new().probe_installed() {
+    ///     println!("{} ({})", installed.info.name, installed.path.display());
+    /// }
+    /// ```
+    pub fn probe_installed(&self) -> Vec<InstalledEditor> {
+        let toolbox = self.toolbox_scripts_dir();
+        probe(self.path_var(), INSTALL_LOCATIONS, toolbox.as_deref())
+    }
+
+    /// Gets the directory where the JetBrains Toolbox App creates the scripts that
+    /// launch its IDEs.
+    fn toolbox_scripts_dir(&self) -> Option<PathBuf> {
+        let var = |key: &str| {
+            self.var_os(OsStr::new(key))
+                .filter(|value| !value.is_empty())
+        };
+        let data_dir = if cfg!(windows) {
+            var("LOCALAPPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            var("HOME").map(|home| Path::new(&home).join("Library/Application Support"))
+        } else {
+            var("XDG_DATA_HOME")
+                .map(PathBuf::from)
+                .or_else(|| var("HOME").map(|home| Path::new(&home).join(".local/share")))
+        };
+        data_dir.map(|dir| dir.join("JetBrains").join("Toolbox").join("scripts"))
+    }
+}
+
+/// Finds the well-known editors on `paths`, in `locations`, or in the `toolbox` scripts
+/// directory.
+fn probe(
+    paths: Option<OsString>,
+    locations: &[(&str, &[&str])],
+    toolbox: Option<&Path>,
+) -> Vec<InstalledEditor> {
+    EditorInfo::all()
+        .iter()
+        .flat_map(|info| info.commands.iter().map(move |command| (info, *command)))
+        .filter_map(|(info, command)| {
+            let path = find_on_paths(command, paths.as_deref())
+                .or_else(|| find_in_locations(command, locations))
+                .or_else(|| find_on_paths(command, toolbox.map(Path::as_os_str)))?;
+            Some(InstalledEditor {
+                info,
+                command,
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Finds `command` on `paths`.
+fn find_on_paths(command: &str, paths: Option<&OsStr>) -> Option<PathBuf> {
+    // NOTE The current directory isn't used, because the commands aren't paths.
+    which::which_in(command, paths, Path::new("")).ok()
+}
+
+/// Finds `command` in its standard install locations.
+fn find_in_locations(command: &str, locations: &[(&str, &[&str])]) -> Option<PathBuf> {
+    locations
+        .iter()
+        .filter(|(name, _)| *name == command)
+        .flat_map(|(_, paths)| paths.iter())
+        .map(PathBuf::from)
+        .find(|path| is_executable(path))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::finder::test_utils::create_executable;
+
+    #[test]
+    fn test_probe() {
+        let bin = tempfile::tempdir().unwrap();
+        let apps = tempfile::tempdir().unwrap();
+        let toolbox = tempfile::tempdir().unwrap();
+        create_executable(&toolbox.path().join("idea"));
+        for command in ["nvim", "not-an-editor"] {
+            create_executable(&bin.path().join(command));
+        }
+        let subl = apps.path().join("subl");
+        create_executable(&subl);
+        let subl_location = subl.to_str().unwrap();
+        let subl_locations = [subl_location];
+        let locations = [("subl", &subl_locations[..])];
+
+        let actual = probe(Some(bin.path().into()), &locations, Some(toolbox.path()));
+        let actual = actual
+            .iter()
+            .map(|installed| (installed.info.name, installed.command))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("Vim", "nvim"),
+                ("Sublime Text", "subl"),
+                ("IntelliJ IDEA", "idea")
+            ],
+            actual
+        );
+    }
+}
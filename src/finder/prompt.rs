@@ -0,0 +1,188 @@
+//! Utilities for letting the user pick an editor, like Debian's `select-editor`.
+use super::Finder;
+use crate::Error;
+use std::ffi::OsString;
+use std::io::{self, BufRead, Write};
+
+/// An editor that the user can pick.
+struct Choice {
+    /// The editor to use if this is picked.
+    editor: OsString,
+    /// Describes where the editor was found.
+    detail: String,
+}
+
+impl Finder {
+    /// Lists the detected editors on stdout, and lets the user pick one from a numbered
+    /// menu on stdin. Returns `None` if the user doesn't pick an editor.
+    ///
+    /// The editors are those from [`Finder::candidates`], followed by those from
+    /// [`Finder::probe_installed`]. Use [`Finder::save_selected_editor`] to remember the
+    /// user's choice.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// match finder.select_interactively().expect("Should prompt") {
+    ///     Some(editor) => println!("You picked {editor:?}"),
+    ///     None => println!("You didn't pick an editor"),
+    /// }
+    /// ```
+    pub fn select_interactively(&self) -> Result<Option<OsString>, Error> {
+        let choices = self.choices();
+        let stdin = io::stdin().lock();
+        let stdout = io::stdout().lock();
+        let index = prompt(&choices, stdin, stdout).map_err(Error::Io)?;
+        Ok(index.map(|index| choices[index].editor.clone()))
+    }
+
+    /// Saves `editor` as the user's choice in `~/.selected_editor`, like Debian's
+    /// `select-editor`. Returns the path to the file. This is only available on Unix.
+    ///
+    /// The choice is found again by [`Source::Debian`](super::Source::Debian).
+    #[cfg(unix)]
+    pub fn save_selected_editor<S>(&self, editor: S) -> Result<std::path::PathBuf, Error>
+    where
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let home = self.var_os(std::ffi::OsStr::new("HOME")).ok_or_else(|| {
+            Error::Io(io::Error::new(io::ErrorKind::NotFound, "$HOME is not set"))
+        })?;
+        let path = std::path::Path::new(&home).join(".selected_editor");
+        let contents = selected_editor_contents(&editor.as_ref().to_string_lossy());
+        std::fs::write(&path, contents).map_err(Error::Io)?;
+        Ok(path)
+    }
+
+    /// Lists the editors that the user can pick, without duplicates.
+    fn choices(&self) -> Vec<Choice> {
+        let mut choices = Vec::<Choice>::new();
+        let detected = self.candidates().map(|candidate| Choice {
+            detail: format!("from {}", candidate.source()),
+            editor: candidate.into_name(),
+        });
+        let installed = self.probe_installed().into_iter().map(|installed| Choice {
+            detail: installed.info.name.to_string(),
+            editor: installed.path.into_os_string(),
+        });
+        for choice in detected.chain(installed) {
+            if !choices.iter().any(|other| other.editor == choice.editor) {
+                choices.push(choice);
+            }
+        }
+        choices
+    }
+}
+
+/// Prints the numbered `choices` to `output`, and reads the user's choice from
+/// `input` until it is valid. Returns `None` if the user enters nothing.
+fn prompt<R, W>(choices: &[Choice], mut input: R, mut output: W) -> io::Result<Option<usize>>
+where
+    R: BufRead,
+    W: Write,
+{
+    if choices.is_empty() {
+        writeln!(output, "No editors were found.")?;
+        return Ok(None);
+    }
+    writeln!(output, "Select an editor.")?;
+    for (number, choice) in (1..).zip(choices) {
+        let editor = choice.editor.to_string_lossy();
+        writeln!(output, "  {number}. {editor} ({})", choice.detail)?;
+    }
+    loop {
+        write!(
+            output,
+            "Choose 1-{}, or press Enter to cancel: ",
+            choices.len()
+        )?;
+        output.flush()?;
+        let mut line = String::new();
+        // NOTE The end of input is the same as entering nothing.
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+        match line.parse::<usize>() {
+            Ok(number) if (1..=choices.len()).contains(&number) => return Ok(Some(number - 1)),
+            _ => writeln!(output, "{line} is not a valid choice.")?,
+        }
+    }
+}
+
+/// Creates the contents of a `.selected_editor` file. The file is sourced by a shell,
+/// so the editor is single-quoted.
+#[cfg(unix)]
+fn selected_editor_contents(editor: &str) -> String {
+    let editor = editor.replace('\'', r"'\''");
+    format!("# Generated by find-editor\nSELECTED_EDITOR='{editor}'\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn choices() -> Vec<Choice> {
+        ["vim", "nano"]
+            .map(|editor| Choice {
+                editor: editor.into(),
+                detail: String::from("test"),
+            })
+            .into()
+    }
+
+    #[rstest]
+    #[case::first("1\n", Some(0))]
+    #[case::second(" 2 \n", Some(1))]
+    #[case::retry("0\nfoo\n2\n", Some(1))]
+    #[case::cancel("\n", None)]
+    #[case::end_of_input("", None)]
+    fn test_prompt(#[case] input: &str, #[case] expected: Option<usize>) {
+        let mut output = Vec::new();
+        let actual = prompt(&choices(), input.as_bytes(), &mut output).unwrap();
+        assert_eq!(expected, actual);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("  1. vim (test)\n  2. nano (test)\n"));
+    }
+
+    #[test]
+    fn test_prompt_no_choices() {
+        let mut output = Vec::new();
+        let actual = prompt(&[], "1\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(None, actual);
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::path("/usr/bin/vim")]
+    #[case::quotes(r#"vim -c 'echo "$HOME"'"#)]
+    #[case::command_substitution("vim $(touch pwned) `id`")]
+    fn test_selected_editor_contents(#[case] editor: &str) {
+        let contents = selected_editor_contents(editor);
+        let actual = crate::finder::debian::parse_selected_editor(&contents);
+        assert_eq!(Some(editor), actual.as_deref());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_selected_editor_contents_sourced() {
+        let editor = r#"vim -c 'echo "$HOME"' $(false)"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".selected_editor");
+        std::fs::write(&path, selected_editor_contents(editor)).unwrap();
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(r#". "$0" && printf %s "$SELECTED_EDITOR""#)
+            .arg(&path)
+            .output()
+            .unwrap();
+        assert_eq!(editor.as_bytes(), output.stdout.as_slice());
+    }
+}
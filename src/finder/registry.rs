@@ -0,0 +1,154 @@
+//! Utilities for finding the default text editor in the Windows registry.
+use super::{Finder, ResolvedEditor};
+use std::ffi::{OsStr, OsString};
+
+impl Finder {
+    /// Finds the program associated with `.txt` files in the Windows registry.
+    #[cfg(windows)]
+    pub(super) fn find_registry_editor<Env>(&self, mut f: Env) -> Option<ResolvedEditor>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        use super::EditorSource;
+
+        let command = user_choice_command().or_else(|| class_command("txtfile"))?;
+        let command = expand_env_vars(&command, &mut f);
+        let editor = quote_program(strip_file_placeholders(&command));
+        (!editor.is_empty()).then(|| ResolvedEditor::new(editor, EditorSource::Registry))
+    }
+
+    /// Finds the default text editor. The registry only exists on Windows.
+    #[cfg(not(windows))]
+    pub(super) fn find_registry_editor<Env>(&self, _f: Env) -> Option<ResolvedEditor>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        None
+    }
+}
+
+/// Gets the command of the program the user chose to open `.txt` files.
+#[cfg(windows)]
+fn user_choice_command() -> Option<String> {
+    use windows_registry::CURRENT_USER;
+
+    const USER_CHOICE: &str =
+        r"Software\Microsoft\Windows\CurrentVersion\Explorer\FileExts\.txt\UserChoice";
+
+    let prog_id = CURRENT_USER
+        .open(USER_CHOICE)
+        .ok()?
+        .get_string("ProgId")
+        .ok()?;
+    class_command(&prog_id)
+}
+
+/// Gets the command to edit, or else open, a file of the given class.
+#[cfg(windows)]
+fn class_command(class: &str) -> Option<String> {
+    use windows_registry::CLASSES_ROOT;
+
+    ["edit", "open"].into_iter().find_map(|verb| {
+        CLASSES_ROOT
+            .open(format!(r"{class}\shell\{verb}\command"))
+            .ok()?
+            .get_string("")
+            .ok()
+    })
+}
+
+/// Expands `%VAR%` references, like `%SystemRoot%`. Unknown variables are left as-is.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn expand_env_vars<Env>(command: &str, mut f: Env) -> String
+where
+    Env: FnMut(&OsStr) -> Option<OsString>,
+{
+    let mut expanded = String::with_capacity(command.len());
+    let mut rest = command;
+    while let Some(start) = rest.find('%') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after
+            .find('%')
+            .map(|end| (&after[..end], end))
+            .filter(|(key, _)| !key.is_empty())
+            .and_then(|(key, end)| f(OsStr::new(key)).map(|value| (value, end)));
+        match value {
+            Some((value, end)) => {
+                expanded.push_str(&value.to_string_lossy());
+                rest = &after[end + 1..];
+            }
+            None => {
+                expanded.push('%');
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Removes the placeholders where the file would be passed, like `"%1"`.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn strip_file_placeholders(command: &str) -> &str {
+    const PLACEHOLDERS: [&str; 5] = ["\"%1\"", "%1", "\"%L\"", "%L", "%*"];
+
+    let mut command = command.trim_end();
+    while let Some(stripped) = PLACEHOLDERS
+        .iter()
+        .find_map(|placeholder| command.strip_suffix(placeholder))
+    {
+        command = stripped.trim_end();
+    }
+    command
+}
+
+/// Quotes the program in a command if it isn't quoted, so that the backslashes in its
+/// path are kept when splitting the command.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn quote_program(command: &str) -> String {
+    if command.is_empty() || command.starts_with('"') {
+        return command.to_string();
+    }
+    let (program, args) = command
+        .to_ascii_lowercase()
+        .find(".exe")
+        .map(|index| command.split_at(index + ".exe".len()))
+        .unwrap_or((command, ""));
+    format!("\"{program}\"{args}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::known(r"%SystemRoot%\notepad.exe", r"C:\Windows\notepad.exe")]
+    #[case::unknown(r"%UNKNOWN%\notepad.exe", r"%UNKNOWN%\notepad.exe")]
+    #[case::unclosed("50% done", "50% done")]
+    fn test_expand_env_vars(#[case] command: &str, #[case] expected: &str) {
+        let f = |key: &OsStr| (key == "SystemRoot").then(|| OsString::from(r"C:\Windows"));
+        assert_eq!(expected, expand_env_vars(command, f));
+    }
+
+    #[rstest]
+    #[case::unquoted(r"C:\Windows\notepad.exe %1", r"C:\Windows\notepad.exe")]
+    #[case::quoted(r#""C:\Editor\edit.exe" -n "%1""#, r#""C:\Editor\edit.exe" -n"#)]
+    #[case::many(r#"edit.exe "%1" %*"#, "edit.exe")]
+    #[case::none("edit.exe", "edit.exe")]
+    fn test_strip_file_placeholders(#[case] command: &str, #[case] expected: &str) {
+        assert_eq!(expected, strip_file_placeholders(command));
+    }
+
+    #[rstest]
+    #[case::unquoted(r"C:\Windows\NOTEPAD.EXE", r#""C:\Windows\NOTEPAD.EXE""#)]
+    #[case::args(
+        r"C:\Program Files\Edit\edit.exe -multiInst",
+        r#""C:\Program Files\Edit\edit.exe" -multiInst"#
+    )]
+    #[case::quoted(r#""C:\edit.exe" -n"#, r#""C:\edit.exe" -n"#)]
+    fn test_quote_program(#[case] command: &str, #[case] expected: &str) {
+        assert_eq!(expected, quote_program(command));
+    }
+}
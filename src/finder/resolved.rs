@@ -0,0 +1,101 @@
+//! Contains the [`ResolvedEditor`] struct which describes where an editor was found.
+use std::ffi::{OsStr, OsString};
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+
+/// An editor found by a [`Finder`](super::Finder), along with where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResolvedEditor {
+    /// The editor's name, which may include arguments.
+    #[cfg_attr(feature = "serde", serde(with = "super::os_serde::os_string"))]
+    name: OsString,
+    /// Where the editor was found.
+    source: EditorSource,
+}
+
+/// Where an editor was found.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EditorSource {
+    /// The `$SUDO_EDITOR` environment variable.
+    SudoEnv,
+    /// One of the extra environment variables configured on the
+    /// [`Finder`](super::Finder).
+    ExtraEnv(#[cfg_attr(feature = "serde", serde(with = "super::os_serde::os_string"))] OsString),
+    /// The editor from your application's config.
+    Config,
+    /// A custom [`Resolver`](super::Resolver), identified by its name.
+    Custom(String),
+    /// One of the standard environment variables, like `$VISUAL` or `$EDITOR`.
+    StandardEnv(
+        #[cfg_attr(feature = "serde", serde(with = "super::os_serde::os_string"))] OsString,
+    ),
+    /// The `$GIT_EDITOR` environment variable.
+    #[cfg(feature = "git")]
+    GitEnv,
+    /// The `core.editor` option in `git config`.
+    #[cfg(feature = "git")]
+    GitConfig,
+    /// The `SELECTED_EDITOR` written to a `.selected_editor` file by Debian's
+    /// `select-editor`.
+    SelectedEditor(PathBuf),
+    /// Debian's `/usr/bin/editor` alternative.
+    DebianAlternatives,
+    /// The program associated with `.txt` files in the Windows registry.
+    #[cfg(feature = "windows-registry")]
+    Registry,
+    /// No editor was configured, so the fallback editor was used.
+    Fallback,
+}
+
+impl ResolvedEditor {
+    /// Creates a new [`ResolvedEditor`].
+    pub(crate) fn new<S>(name: S, source: EditorSource) -> Self
+    where
+        S: Into<OsString>,
+    {
+        let name = name.into();
+        Self { name, source }
+    }
+
+    /// The editor's name, which may include arguments (e.g. `code --wait`).
+    #[inline]
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    /// Where the editor was found.
+    #[inline]
+    pub fn source(&self) -> &EditorSource {
+        &self.source
+    }
+
+    /// Converts this into the editor's name.
+    #[inline]
+    pub fn into_name(self) -> OsString {
+        self.name
+    }
+}
+
+impl Display for EditorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExtraEnv(key) | Self::StandardEnv(key) => write!(f, "${}", key.to_string_lossy()),
+            Self::SudoEnv => write!(f, "$SUDO_EDITOR"),
+            Self::Config => write!(f, "the config"),
+            Self::Custom(name) => write!(f, "{name}"),
+            #[cfg(feature = "git")]
+            Self::GitEnv => write!(f, "$GIT_EDITOR"),
+            #[cfg(feature = "git")]
+            Self::GitConfig => write!(f, "git's core.editor"),
+            Self::SelectedEditor(path) => write!(f, "{}", path.display()),
+            Self::DebianAlternatives => write!(f, "Debian's editor alternative"),
+            #[cfg(feature = "windows-registry")]
+            Self::Registry => write!(f, "the Windows registry"),
+            Self::Fallback => write!(f, "the fallback editor"),
+        }
+    }
+}
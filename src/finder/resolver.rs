@@ -0,0 +1,52 @@
+//! Contains the [`Resolver`] trait for custom editor sources.
+use std::ffi::OsString;
+
+/// A custom source of an editor, like your application's config file.
+///
+/// Add one to a [`Finder`](super::Finder) with
+/// [`FinderBuilder::resolver`](super::FinderBuilder::resolver). Closures that return an
+/// `Option<OsString>` are also resolvers.
+///
+/// # Example
+///
+/// ```rust
+/// use find_editor::{Finder, Resolver};
+/// use std::ffi::OsString;
+///
+/// struct MyConfig {
+///     editor: Option<String>,
+/// }
+///
+/// impl Resolver for MyConfig {
+///     fn resolve(&self) -> Option<OsString> {
+///         self.editor.as_ref().map(OsString::from)
+///     }
+///
+///     fn name(&self) -> &str {
+///         "my config file"
+///     }
+/// }
+///
+/// let config = MyConfig { editor: Some(String::from("nano")) };
+/// let finder = Finder::builder().resolver(config).build();
+/// ```
+pub trait Resolver: Send + Sync {
+    /// Finds an editor, or returns `None` if this source doesn't define one.
+    fn resolve(&self) -> Option<OsString>;
+
+    /// A short name for this source, used by
+    /// [`EditorSource::Custom`](super::EditorSource::Custom).
+    fn name(&self) -> &str {
+        "custom resolver"
+    }
+}
+
+impl<F> Resolver for F
+where
+    F: Fn() -> Option<OsString> + Send + Sync,
+{
+    #[inline]
+    fn resolve(&self) -> Option<OsString> {
+        self()
+    }
+}
@@ -0,0 +1,140 @@
+//! Contains the [`EditorSession`] struct, which kills an editor when dropped.
+use super::Finder;
+use crate::Error;
+use std::path::Path;
+use std::process::{Child, ExitStatus};
+
+/// An editor's process that is killed when dropped, unless it has already exited.
+///
+/// This prevents orphaned editor processes, like when your application panics while
+/// the editor is open. Create one with [`Finder::spawn_editor_session`] or
+/// [`OpenOptions::spawn_session`](super::OpenOptions::spawn_session).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use find_editor::Finder;
+///
+/// let finder = Finder::new();
+/// let mut session = finder
+///     .spawn_editor_session("config.toml")
+///     .expect("Should open an editor");
+/// // If this panics, the editor is killed.
+/// session.wait().expect("Should be able to wait for the editor");
+/// ```
+#[derive(Debug)]
+pub struct EditorSession {
+    /// The editor's process, or `None` after it's been taken.
+    child: Option<Child>,
+}
+
+impl Finder {
+    /// Opens an editor to edit `file` without waiting, and returns an
+    /// [`EditorSession`] that kills the editor when dropped.
+    ///
+    /// See [`Finder::spawn_editor`] for more information.
+    pub fn spawn_editor_session<P>(&self, file: P) -> Result<EditorSession, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.open_options().spawn_session(file)
+    }
+}
+
+impl EditorSession {
+    /// Creates a new [`EditorSession`] that kills `child` when dropped.
+    #[inline]
+    pub fn new(child: Child) -> Self {
+        Self { child: Some(child) }
+    }
+
+    /// The editor's process ID.
+    pub fn id(&self) -> u32 {
+        self.child().id()
+    }
+
+    /// Waits for the editor to exit, and returns its exit status.
+    pub fn wait(&mut self) -> Result<ExitStatus, Error> {
+        self.child_mut().wait().map_err(Error::Io)
+    }
+
+    /// Returns the editor's exit status if it has exited, without waiting.
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>, Error> {
+        self.child_mut().try_wait().map_err(Error::Io)
+    }
+
+    /// Kills the editor, and waits for it to exit.
+    pub fn kill(&mut self) -> Result<(), Error> {
+        let child = self.child_mut();
+        child.kill().map_err(Error::Io)?;
+        child.wait().map_err(Error::Io)?;
+        Ok(())
+    }
+
+    /// Returns the editor's process without killing it, so it can outlive this session.
+    pub fn into_child(mut self) -> Child {
+        self.child
+            .take()
+            .expect("The child should not have been taken")
+    }
+
+    /// The editor's process.
+    fn child(&self) -> &Child {
+        self.child
+            .as_ref()
+            .expect("The child should not have been taken")
+    }
+
+    /// The editor's process, mutably.
+    fn child_mut(&mut self) -> &mut Child {
+        self.child
+            .as_mut()
+            .expect("The child should not have been taken")
+    }
+}
+
+impl From<Child> for EditorSession {
+    #[inline]
+    fn from(child: Child) -> Self {
+        Self::new(child)
+    }
+}
+
+impl Drop for EditorSession {
+    fn drop(&mut self) {
+        let Some(child) = self.child.as_mut() else {
+            return;
+        };
+        // NOTE Errors are ignored, because there's nothing more to do with the editor.
+        if let Ok(None) = child.try_wait() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_drop_kills_editor() {
+        let finder = Finder::builder()
+            .sources([])
+            .fallback("sh -c 'sleep 10'")
+            .build();
+        let session = finder.spawn_editor_session("f.txt").unwrap();
+        let pid = session.id();
+        drop(session);
+        // NOTE The killed process has been reaped, so it no longer exists.
+        assert!(!Path::new(&format!("/proc/{pid}")).exists());
+    }
+
+    #[test]
+    fn test_wait() {
+        let finder = Finder::builder().sources([]).fallback("true").build();
+        let mut session = finder.spawn_editor_session("f.txt").unwrap();
+        assert!(session.wait().unwrap().success());
+    }
+}
@@ -0,0 +1,36 @@
+//! Contains the [`Source`] enum for ordering where editors are searched for.
+
+/// A kind of source that a [`Finder`](super::Finder) searches for an editor.
+///
+/// Use [`FinderBuilder::sources`](super::FinderBuilder::sources) to choose which
+/// sources are searched, and in which order.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Source {
+    /// The `$SUDO_EDITOR` environment variable, used by `sudoedit`.
+    Sudo,
+    /// The extra environment variables configured on the [`Finder`](super::Finder).
+    ExtraEnv,
+    /// The editor from your application's config, set with
+    /// [`FinderBuilder::config_value`](super::FinderBuilder::config_value).
+    Config,
+    /// Any custom [`Resolver`](super::Resolver)s, like your application's config file.
+    Custom,
+    /// The standard environment variables, `$VISUAL` and `$EDITOR` by default.
+    StandardEnv,
+    /// Git's editor, from `$GIT_EDITOR` or `git config core.editor`.
+    #[cfg(feature = "git")]
+    Git,
+    /// Debian's selected editor and editor alternative.
+    Debian,
+    /// The program associated with `.txt` files in the Windows registry.
+    #[cfg(feature = "windows-registry")]
+    WindowsRegistry,
+}
+
+impl Source {
+    /// The sources searched by default, in order.
+    pub(crate) const DEFAULT: [Self; 3] = [Self::ExtraEnv, Self::Custom, Self::StandardEnv];
+}
@@ -0,0 +1,197 @@
+//! Contains the [`SplitStyle`] enum for splitting an editor into words.
+use crate::ParseError;
+use std::ffi::{OsStr, OsString};
+
+/// The rules used to split an editor, like `code --wait`, into a command and its
+/// arguments.
+///
+/// Use [`FinderBuilder::split_style`](super::FinderBuilder::split_style) to override
+/// the default for the current platform.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SplitStyle {
+    /// POSIX shell rules, where a backslash escapes the next character. This is the
+    /// default on every platform except Windows.
+    Posix,
+    /// Windows command-line rules, where backslashes are path separators, so
+    /// `"C:\Program Files\Notepad++\notepad++.exe" -multiInst` keeps its backslashes.
+    /// A backslash only escapes a `"` (or backslashes before a `"`). An absolute path to
+    /// an existing file isn't split, even without quotes. This is the default on
+    /// Windows.
+    Windows,
+}
+
+impl SplitStyle {
+    /// Splits `s` into words.
+    pub(super) fn split(self, s: &str) -> Result<Vec<String>, ParseError> {
+        match self {
+            Self::Posix => shell_words::split(s),
+            Self::Windows => Ok(split_windows(s)),
+        }
+    }
+}
+
+impl Default for SplitStyle {
+    fn default() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Posix
+        }
+    }
+}
+
+/// The first of the characters that stand in for bytes that aren't valid UTF-8. They
+/// are in a private use area, so they're very unlikely to be in an editor's name.
+#[cfg(unix)]
+const BYTE_CHAR_START: u32 = 0x10_FF00;
+
+/// Converts `s` to a [`String`] that can be split into words, and then converted back
+/// with [`decode`] without losing any bytes.
+///
+/// On Unix, each byte that isn't valid UTF-8 is replaced with a stand-in character.
+/// On other platforms, invalid characters are replaced with `U+FFFD`.
+pub(super) fn encode(s: &OsStr) -> String {
+    if let Some(s) = s.to_str() {
+        return s.to_string();
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let mut encoded = String::with_capacity(s.len());
+        for chunk in s.as_bytes().utf8_chunks() {
+            encoded.push_str(chunk.valid());
+            encoded.extend(chunk.invalid().iter().map(|byte| {
+                char::from_u32(BYTE_CHAR_START + u32::from(*byte))
+                    .expect("Stand-in characters should be valid")
+            }));
+        }
+        encoded
+    }
+    #[cfg(not(unix))]
+    {
+        s.to_string_lossy().into_owned()
+    }
+}
+
+/// Converts a [`String`] created by [`encode`] back to an [`OsString`].
+pub(super) fn decode(s: String) -> OsString {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        let stand_ins = BYTE_CHAR_START..=BYTE_CHAR_START + 0xFF;
+        if !s.chars().any(|c| stand_ins.contains(&u32::from(c))) {
+            return s.into();
+        }
+        let mut bytes = Vec::with_capacity(s.len());
+        for c in s.chars() {
+            let code = u32::from(c);
+            if stand_ins.contains(&code) {
+                bytes.push((code - BYTE_CHAR_START) as u8);
+            } else {
+                bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            }
+        }
+        OsString::from_vec(bytes)
+    }
+    #[cfg(not(unix))]
+    {
+        s.into()
+    }
+}
+
+/// Splits `s` into words like `CommandLineToArgvW`.
+fn split_windows(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    let mut backslashes = 0;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                backslashes += 1;
+                in_word = true;
+                continue;
+            }
+            '"' => {
+                // NOTE Each pair of backslashes before a quote is one backslash, and an
+                //      odd backslash escapes the quote.
+                word.extend(std::iter::repeat_n('\\', backslashes / 2));
+                if backslashes % 2 == 1 {
+                    word.push('"');
+                } else if quoted && chars.peek() == Some(&'"') {
+                    // NOTE "" inside quotes is a literal quote.
+                    chars.next();
+                    word.push('"');
+                } else {
+                    quoted = !quoted;
+                }
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                word.extend(std::iter::repeat_n('\\', backslashes));
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                }
+                in_word = false;
+            }
+            c => {
+                word.extend(std::iter::repeat_n('\\', backslashes));
+                word.push(c);
+                in_word = true;
+            }
+        }
+        backslashes = 0;
+    }
+    word.extend(std::iter::repeat_n('\\', backslashes));
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::simple("code --wait", &["code", "--wait"])]
+    #[case::quoted_path(
+        r#""C:\Program Files\Notepad++\notepad++.exe" -multiInst"#,
+        &[r"C:\Program Files\Notepad++\notepad++.exe", "-multiInst"],
+    )]
+    #[case::unquoted_path(r"C:\Windows\notepad.exe", &[r"C:\Windows\notepad.exe"])]
+    #[case::escaped_quote(r#"edit \"a\""#, &["edit", r#""a""#])]
+    #[case::backslashes_before_quote(r#"edit "C:\dir\\""#, &["edit", r"C:\dir\"])]
+    #[case::double_quote(r#"edit "a""b""#, &["edit", r#"a"b"#])]
+    #[case::empty_arg(r#"edit """#, &["edit", ""])]
+    #[case::extra_whitespace("  edit   file  ", &["edit", "file"])]
+    fn test_split_windows(#[case] s: &str, #[case] expected: &[&str]) {
+        assert_eq!(expected, split_windows(s));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_encode_decode() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let s = OsStr::from_bytes(b"'caf\xe9 edit' --fast");
+        let words = SplitStyle::Posix.split(&encode(s)).unwrap();
+        let words = words.into_iter().map(decode).collect::<Vec<_>>();
+        assert_eq!(OsStr::from_bytes(b"caf\xe9 edit"), words[0]);
+        assert_eq!("--fast", words[1]);
+    }
+
+    #[test]
+    fn test_split_posix() {
+        let actual = SplitStyle::Posix.split(r"edit C:\dir").unwrap();
+        assert_eq!(vec!["edit", "C:dir"], actual);
+    }
+}
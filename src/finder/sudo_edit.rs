@@ -0,0 +1,279 @@
+//! Utilities for editing a privileged file without running the editor as root.
+use super::Finder;
+use crate::Error;
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+impl Finder {
+    /// Edits `file` like `sudoedit`, without running the editor with elevated
+    /// privileges. Returns whether the file was changed. This is only available on
+    /// Unix.
+    ///
+    /// Running an editor as root can be a privilege escalation, because editors can run
+    /// commands. Instead, `file` is copied to a temporary file that is owned by the user
+    /// who invoked `sudo` (`$SUDO_UID` and `$SUDO_GID`), and the editor is run as that
+    /// user. After the editor is closed, the edited content is copied back to `file`
+    /// only if it changed. If `$SUDO_UID` isn't set, the editor is run as the current
+    /// user.
+    ///
+    /// Like `sudoedit`, this refuses to edit symbolic links. Consider using
+    /// [`FinderBuilder::sudo_editor`](super::FinderBuilder::sudo_editor) to look up
+    /// `$SUDO_EDITOR`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use find_editor::Finder;
+    ///
+    /// let finder = Finder::builder().sudo_editor(true).build();
+    /// let changed = finder
+    ///     .sudo_edit("/etc/hosts")
+    ///     .expect("Should be able to edit the file");
+    /// ```
+    #[inline]
+    pub fn sudo_edit<P>(&self, file: P) -> Result<bool, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.sudo_edit_inner(file.as_ref(), |key| self.var_os(key))
+    }
+
+    /// Edits `file` like `sudoedit`.
+    fn sudo_edit_inner<Env>(&self, file: &Path, mut f: Env) -> Result<bool, Error>
+    where
+        Env: FnMut(&OsStr) -> Option<OsString>,
+    {
+        let user = sudo_user(&mut f).map_err(Error::Io)?;
+        let is_symlink = fs::symlink_metadata(file).is_ok_and(|meta| meta.is_symlink());
+        if is_symlink {
+            let message = format!("{} is a symbolic link", file.display());
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                message,
+            )));
+        }
+        let original = match fs::read(file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(Error::Io(e)),
+        };
+
+        // NOTE The copy keeps the file's name so that editors can pick syntax
+        //      highlighting.
+        let dir = tempfile::Builder::new()
+            .prefix("find-editor-")
+            .tempdir()
+            .map_err(Error::Io)?;
+        let file_name = file.file_name().unwrap_or(OsStr::new("file"));
+        let copy = dir.path().join(file_name);
+        let mut copy_file = File::create_new(&copy).map_err(Error::Io)?;
+        copy_file.write_all(&original).map_err(Error::Io)?;
+        drop(copy_file);
+        fs::set_permissions(&copy, fs::Permissions::from_mode(0o600)).map_err(Error::Io)?;
+
+        let options = self.open_options();
+        let options = match user {
+            Some((uid, gid)) => {
+                // NOTE The editor may replace the file, so it needs to own the
+                //      directory too.
+                std::os::unix::fs::chown(dir.path(), Some(uid), Some(gid)).map_err(Error::Io)?;
+                std::os::unix::fs::chown(&copy, Some(uid), Some(gid)).map_err(Error::Io)?;
+                options.user(uid, gid)
+            }
+            None => options,
+        };
+        options.open(&copy)?;
+
+        let edited = read_copy(&copy, user.map(|(uid, _)| uid)).map_err(Error::Io)?;
+        if edited == original {
+            return Ok(false);
+        }
+        write_back(file, &edited).map_err(Error::Io)?;
+        Ok(true)
+    }
+}
+
+/// Reads the edited `copy`, which must be a regular file that's owned by `uid`.
+///
+/// The user owns the directory of the copy, so the copy could have been replaced with a
+/// symbolic link to a file that the user can't read. Like `sudoedit`, the copy is opened
+/// without following symbolic links, and its metadata is checked after opening it.
+fn read_copy(copy: &Path, uid: Option<u32>) -> io::Result<Vec<u8>> {
+    use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
+
+    // NOTE O_NONBLOCK keeps a FIFO from blocking the open. It doesn't affect reading
+    //      regular files.
+    let mut copy_file = fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NOFOLLOW | libc::O_NONBLOCK)
+        .open(copy)?;
+    let meta = copy_file.metadata()?;
+    let uid = uid.unwrap_or_else(|| meta.uid());
+    if !meta.is_file() || meta.uid() != uid {
+        let message = format!("{} is not a regular file owned by the user", copy.display());
+        return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+    }
+    let mut content = Vec::new();
+    io::Read::read_to_end(&mut copy_file, &mut content)?;
+    Ok(content)
+}
+
+/// Writes the `content` to `file` by replacing it with a temporary file, so that a
+/// symbolic link at `file` is replaced instead of followed. The file's permissions and
+/// owner are kept.
+fn write_back(file: &Path, content: &[u8]) -> io::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+    temp.write_all(content)?;
+    match fs::symlink_metadata(file) {
+        Ok(meta) => {
+            temp.as_file().set_permissions(meta.permissions())?;
+            // NOTE Only root can give the file to another user, so this is allowed to
+            //      fail when the file belongs to the current user anyway.
+            if let Err(e) =
+                std::os::unix::fs::fchown(temp.as_file(), Some(meta.uid()), Some(meta.gid()))
+            {
+                if e.kind() != io::ErrorKind::PermissionDenied {
+                    return Err(e);
+                }
+            }
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            temp.as_file()
+                .set_permissions(fs::Permissions::from_mode(0o644))?;
+        }
+        Err(e) => return Err(e),
+    }
+    temp.as_file().sync_all()?;
+    temp.persist(file).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Gets the user and group IDs of the user who invoked `sudo`, or `None` if `sudo`
+/// wasn't used.
+fn sudo_user<Env>(mut f: Env) -> io::Result<Option<(u32, u32)>>
+where
+    Env: FnMut(&OsStr) -> Option<OsString>,
+{
+    let mut id = |key: &str| -> io::Result<Option<u32>> {
+        let Some(value) = f(OsStr::new(key)) else {
+            return Ok(None);
+        };
+        value
+            .to_str()
+            .and_then(|value| value.parse().ok())
+            .map(Some)
+            .ok_or_else(|| {
+                let message = format!("${key} is not a valid ID");
+                io::Error::new(io::ErrorKind::InvalidData, message)
+            })
+    };
+    let Some(uid) = id("SUDO_UID")? else {
+        return Ok(None);
+    };
+    let gid = id("SUDO_GID")?.unwrap_or(uid);
+    Ok(Some((uid, gid)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::unchanged("true", false, "content")]
+    #[case::changed(r#"sh -c 'printf "!" >> "$0"'"#, true, "content!")]
+    fn test_sudo_edit(#[case] editor: &str, #[case] expected: bool, #[case] content: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("hosts");
+        fs::write(&file, "content").unwrap();
+        let finder = Finder::builder().sources([]).fallback(editor).build();
+        let actual = finder.sudo_edit_inner(&file, |_| None).unwrap();
+        assert_eq!(expected, actual);
+        assert_eq!(content, fs::read_to_string(&file).unwrap());
+    }
+
+    #[test]
+    fn test_sudo_edit_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("hosts");
+        fs::write(&file, "content").unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&file, &link).unwrap();
+        let finder = Finder::builder().sources([]).fallback("true").build();
+        assert!(finder.sudo_edit_inner(&link, |_| None).is_err());
+    }
+
+    #[test]
+    fn test_read_copy_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret = dir.path().join("secret");
+        fs::write(&secret, "secret").unwrap();
+        let copy = dir.path().join("hosts");
+        std::os::unix::fs::symlink(&secret, &copy).unwrap();
+        assert!(read_copy(&copy, None).is_err());
+    }
+
+    #[test]
+    fn test_read_copy_other_owner() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let copy = dir.path().join("hosts");
+        fs::write(&copy, "content").unwrap();
+        let uid = fs::metadata(&copy).unwrap().uid();
+        assert_eq!(b"content", read_copy(&copy, Some(uid)).unwrap().as_slice());
+        assert!(read_copy(&copy, Some(uid.wrapping_add(1))).is_err());
+    }
+
+    #[test]
+    fn test_write_back_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = dir.path().join("other");
+        fs::write(&other, "other").unwrap();
+        let file = dir.path().join("hosts");
+        std::os::unix::fs::symlink(&other, &file).unwrap();
+        write_back(&file, b"content").unwrap();
+        assert_eq!("other", fs::read_to_string(&other).unwrap());
+        assert_eq!("content", fs::read_to_string(&file).unwrap());
+        assert!(!fs::symlink_metadata(&file).unwrap().is_symlink());
+    }
+
+    #[test]
+    fn test_write_back_permissions() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("hosts");
+        fs::write(&file, "content").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o640)).unwrap();
+        write_back(&file, b"edited").unwrap();
+        let mode = fs::metadata(&file).unwrap().permissions().mode();
+        assert_eq!(0o640, mode & 0o777);
+        assert_eq!("edited", fs::read_to_string(&file).unwrap());
+    }
+
+    #[rstest]
+    #[case::unset(None, None, Some(None))]
+    #[case::both(Some("1000"), Some("100"), Some(Some((1000, 100))))]
+    #[case::uid_only(Some("1000"), None, Some(Some((1000, 1000))))]
+    #[case::invalid(Some("root"), None, None)]
+    fn test_sudo_user(
+        #[case] uid: Option<&str>,
+        #[case] gid: Option<&str>,
+        #[case] expected: Option<Option<(u32, u32)>>,
+    ) {
+        let f = |key: &OsStr| match key.to_str() {
+            Some("SUDO_UID") => uid.map(OsString::from),
+            Some("SUDO_GID") => gid.map(OsString::from),
+            _ => None,
+        };
+        assert_eq!(expected, sudo_user(f).ok());
+    }
+}
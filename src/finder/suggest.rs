@@ -0,0 +1,100 @@
+//! Utilities for suggesting a command when an editor's command can't be found.
+use super::is_executable;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
+
+/// Suggests a command on `path`, the value of `$PATH`, with a name similar to
+/// `command`, like `vim` for `virm`.
+pub(super) fn suggest(command: &str, path: Option<OsString>) -> Option<String> {
+    let path = path.unwrap_or_default();
+    suggest_in(command, env::split_paths(&path))
+}
+
+/// Suggests a command in `dirs` with a name similar to `command`.
+fn suggest_in<I>(command: &str, dirs: I) -> Option<String>
+where
+    I: IntoIterator<Item = PathBuf>,
+{
+    // NOTE A path isn't misspelled in a way that searching $PATH can fix.
+    if command.contains(std::path::is_separator) {
+        return None;
+    }
+    let max_distance = (command.chars().count() / 3).max(1);
+    dirs.into_iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            is_executable(&entry.path()).then(|| command_name(entry.file_name()))?
+        })
+        .filter(|name| name != command)
+        .map(|name| (distance(command, &name), name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, name)| name)
+}
+
+/// Gets the name a file would be called by as a command.
+fn command_name(file_name: OsString) -> Option<String> {
+    let name = file_name.into_string().ok()?;
+    // NOTE On Windows, executables are called without their extension.
+    if cfg!(windows) {
+        if let Some((stem, _)) = name.rsplit_once('.') {
+            return Some(stem.to_string());
+        }
+    }
+    Some(name)
+}
+
+/// Calculates the Levenshtein distance between two strings.
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            let insertion = current[j] + 1;
+            let deletion = previous[j + 1] + 1;
+            current.push(substitution.min(insertion).min(deletion));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use crate::finder::test_utils::create_executable;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::same("vim", "vim", 0)]
+    #[case::insertion("virm", "vim", 1)]
+    #[case::substitution("namo", "nano", 1)]
+    #[case::different("emacs", "vi", 5)]
+    fn test_distance(#[case] a: &str, #[case] b: &str, #[case] expected: usize) {
+        assert_eq!(expected, distance(a, b));
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::misspelled("virm", Some("vim"))]
+    #[case::too_different("emacs", None)]
+    #[case::path("/bin/virm", None)]
+    fn test_suggest_in(#[case] command: &str, #[case] expected: Option<&str>) {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["vim", "nano"] {
+            let path = dir.path().join(name);
+            create_executable(&path);
+        }
+        // NOTE Files that aren't executable aren't commands.
+        fs::write(dir.path().join("vi"), "").unwrap();
+        let actual = suggest_in(command, [dir.path().to_path_buf()]);
+        assert_eq!(expected, actual.as_deref());
+    }
+}
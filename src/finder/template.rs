@@ -0,0 +1,104 @@
+//! Utilities for editors with placeholders in their arguments, like
+//! `myedit --file={file} --line={line}`.
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// The placeholder for the file.
+const FILE: &str = "{file}";
+/// The placeholder for the line.
+const LINE: &str = "{line}";
+/// The placeholder for the column.
+const COLUMN: &str = "{column}";
+
+/// Checks if any of the editor's `args` has a placeholder.
+pub(super) fn has_placeholders(args: &[OsString]) -> bool {
+    args.iter().any(|arg| {
+        arg.to_str()
+            .is_some_and(|arg| [FILE, LINE, COLUMN].iter().any(|p| arg.contains(p)))
+    })
+}
+
+/// Substitutes the placeholders in the editor's `args`, and returns the arguments and
+/// any files that still need to be passed after them.
+///
+/// An argument with `{file}` is repeated for each of `files`, so it's removed if there
+/// are no files. If no argument has `{file}`, the files are returned to be passed as
+/// usual. `{line}` and `{column}` are `1` if they aren't set.
+pub(super) fn substitute(
+    args: &[OsString],
+    files: Vec<PathBuf>,
+    line: Option<usize>,
+    column: Option<usize>,
+) -> (Vec<OsString>, Vec<OsString>) {
+    let line = line.unwrap_or(1).to_string();
+    let column = column.unwrap_or(1).to_string();
+    let has_file = args
+        .iter()
+        .any(|arg| arg.to_str().is_some_and(|arg| arg.contains(FILE)));
+    let mut new_args = Vec::new();
+    for arg in args {
+        // NOTE Arguments that aren't valid unicode can't have placeholders.
+        let Some(arg) = arg.to_str() else {
+            new_args.push(arg.clone());
+            continue;
+        };
+        let arg = arg.replace(LINE, &line).replace(COLUMN, &column);
+        if !arg.contains(FILE) {
+            new_args.push(arg.into());
+            continue;
+        }
+        new_args.extend(files.iter().map(|file| {
+            let mut parts = arg.split(FILE);
+            let mut new_arg = OsString::from(parts.next().unwrap_or_default());
+            for part in parts {
+                new_arg.push(file);
+                new_arg.push(part);
+            }
+            new_arg
+        }));
+    }
+    let files = if has_file {
+        Vec::new()
+    } else {
+        files.into_iter().map(PathBuf::into_os_string).collect()
+    };
+    (new_args, files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::file(&["--file={file}"], true)]
+    #[case::line(&["+{line}"], true)]
+    #[case::column(&["-c", "{column}"], true)]
+    #[case::none(&["--wait", "{files}"], false)]
+    fn test_has_placeholders(#[case] args: &[&str], #[case] expected: bool) {
+        let args = args.iter().map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(expected, has_placeholders(&args));
+    }
+
+    #[rstest]
+    #[case::file_and_line(&["--file={file}", "--line={line}"], &["f.txt"], Some(10), None, &["--file=f.txt", "--line=10"], &[])]
+    #[case::default_position(&["{file}:{line}:{column}"], &["f.txt"], None, None, &["f.txt:1:1"], &[])]
+    #[case::repeated(&["-w", "--open", "{file}"], &["a.txt", "b.txt"], None, None, &["-w", "--open", "a.txt", "b.txt"], &[])]
+    #[case::twice(&["{file}={file}"], &["f.txt"], None, None, &["f.txt=f.txt"], &[])]
+    #[case::no_files(&["--file={file}", "-n"], &[], None, None, &["-n"], &[])]
+    #[case::line_only(&["+{line}"], &["f.txt"], Some(3), None, &["+3"], &["f.txt"])]
+    fn test_substitute(
+        #[case] args: &[&str],
+        #[case] files: &[&str],
+        #[case] line: Option<usize>,
+        #[case] column: Option<usize>,
+        #[case] expected_args: &[&str],
+        #[case] expected_files: &[&str],
+    ) {
+        let args = args.iter().map(OsString::from).collect::<Vec<_>>();
+        let files = files.iter().map(PathBuf::from).collect();
+        let (actual_args, actual_files) = substitute(&args, files, line, column);
+        assert_eq!(expected_args, actual_args.as_slice());
+        assert_eq!(expected_files, actual_files.as_slice());
+    }
+}
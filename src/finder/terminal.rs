@@ -0,0 +1,73 @@
+//! Utilities for saving and restoring the terminal's state.
+use std::fmt;
+
+/// A snapshot of the terminal's settings.
+#[derive(Clone, Copy)]
+pub(super) struct TerminalState {
+    /// The file descriptor of the terminal.
+    #[cfg(unix)]
+    fd: libc::c_int,
+    /// The terminal's settings.
+    #[cfg(unix)]
+    termios: libc::termios,
+}
+
+impl TerminalState {
+    /// Saves the settings of the first of stdin, stdout, and stderr that is a terminal.
+    /// Returns `None` if none of them are terminals.
+    #[cfg(unix)]
+    pub(super) fn save() -> Option<Self> {
+        use std::mem::MaybeUninit;
+
+        [libc::STDIN_FILENO, libc::STDOUT_FILENO, libc::STDERR_FILENO]
+            .into_iter()
+            // SAFETY: isatty only reads the file descriptor.
+            .filter(|fd| unsafe { libc::isatty(*fd) } == 1)
+            .find_map(|fd| {
+                let mut termios = MaybeUninit::uninit();
+                // SAFETY: The pointer is valid, and termios is initialized when this
+                //         succeeds.
+                if unsafe { libc::tcgetattr(fd, termios.as_mut_ptr()) } != 0 {
+                    return None;
+                }
+                let termios = unsafe { termios.assume_init() };
+                Some(Self { fd, termios })
+            })
+    }
+
+    /// Saves the terminal's settings. Terminal settings are only supported on Unix.
+    #[cfg(not(unix))]
+    pub(super) fn save() -> Option<Self> {
+        None
+    }
+
+    /// Restores the saved settings.
+    #[cfg(unix)]
+    pub(super) fn restore(&self) {
+        // SAFETY: The settings were returned by tcgetattr.
+        // NOTE Errors are ignored, because there's nothing more to do with the terminal.
+        unsafe { libc::tcsetattr(self.fd, libc::TCSANOW, &self.termios) };
+    }
+
+    /// Restores the saved settings. Terminal settings are only supported on Unix.
+    #[cfg(not(unix))]
+    pub(super) fn restore(&self) {}
+}
+
+impl fmt::Debug for TerminalState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("TerminalState");
+        #[cfg(unix)]
+        debug.field("fd", &self.fd);
+        debug.finish_non_exhaustive()
+    }
+}
+
+/// Restores the terminal's settings when dropped.
+pub(super) struct RestoreTerminal(pub(super) TerminalState);
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        self.0.restore();
+    }
+}
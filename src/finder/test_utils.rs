@@ -0,0 +1,24 @@
+//! Fixtures shared by the tests.
+#[cfg(feature = "open")]
+use super::Finder;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+/// Creates an executable file with the given contents.
+pub(super) fn create_script(path: &Path, contents: &str) {
+    fs::write(path, contents).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// Creates an empty executable file.
+pub(super) fn create_executable(path: &Path) {
+    create_script(path, "");
+}
+
+/// Creates an empty executable file and a [`Finder`] that only falls back to it.
+#[cfg(feature = "open")]
+pub(super) fn fallback_finder(path: &Path) -> Finder {
+    create_executable(path);
+    Finder::builder().sources([]).fallback(path).build()
+}
@@ -0,0 +1,326 @@
+//! Utilities for waiting for an editor's process.
+use super::terminal::{RestoreTerminal, TerminalState};
+use crate::Error;
+use std::fmt;
+use std::io;
+use std::process::{Child, ChildStderr, ExitStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How to wait for an editor's process.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Waiter {
+    /// How long to wait before the editor is terminated.
+    pub(super) timeout: Option<Duration>,
+    /// When set to `true`, the editor is terminated.
+    pub(super) cancel: Option<Arc<AtomicBool>>,
+    /// If a non-zero exit status is an error.
+    pub(super) fail_on_nonzero: bool,
+    /// If the editor's stderr is captured, to be included in the error when it fails.
+    pub(super) capture_stderr: bool,
+    /// If `SIGINT` and `SIGQUIT` are ignored while waiting.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    pub(super) ignore_signals: bool,
+    /// If the terminal's settings are restored after the editor exits.
+    pub(super) restore_terminal: bool,
+    /// The terminal's settings from before the editor was opened.
+    pub(super) terminal: Option<TerminalState>,
+    /// Called with the editor's exit status.
+    pub(super) after_exit: Option<AfterExit>,
+}
+
+/// A hook that's called with the editor's exit status.
+type AfterExitHook = dyn FnMut(&ExitStatus) + Send;
+
+/// A hook that's called with the editor's exit status, shared by every copy of the
+/// [`Waiter`].
+#[derive(Clone)]
+pub(super) struct AfterExit(pub(super) Arc<Mutex<AfterExitHook>>);
+
+impl fmt::Debug for AfterExit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AfterExit").finish_non_exhaustive()
+    }
+}
+
+impl Waiter {
+    /// The longest time to sleep between checks on the editor.
+    const MAX_POLL_INTERVAL: Duration = Duration::from_millis(100);
+    /// How long a terminated editor has to exit before it is killed.
+    #[cfg(unix)]
+    const KILL_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+    /// Waits for the editor, terminating it if it takes too long or is cancelled.
+    pub(super) fn wait(&self, mut child: Child) -> Result<ExitStatus, Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("finder.wait", pid = child.id()).entered();
+        let _restore = self.terminal.map(RestoreTerminal);
+        // NOTE Signals are ignored after spawning, because the editor would inherit
+        //      ignored signals.
+        #[cfg(unix)]
+        let _ignored = self
+            .ignore_signals
+            .then(IgnoredSignals::new)
+            .transpose()
+            .map_err(Error::Io)?;
+        let capture = self
+            .capture_stderr
+            .then(|| child.stderr.take())
+            .flatten()
+            .map(StderrCapture::spawn);
+        let check = |status| {
+            self.check(status).map_err(|e| match (e, capture) {
+                (Error::EditorExited { status, .. }, Some(capture)) => Error::EditorExited {
+                    status,
+                    stderr: Some(capture.finish()),
+                },
+                (e, _) => e,
+            })
+        };
+        if self.timeout.is_none() && self.cancel.is_none() {
+            let status = child.wait().map_err(Error::Io)?;
+            return check(status);
+        }
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let cancel = self.cancel.as_deref();
+        match wait_until(&mut child, deadline, cancel).map_err(Error::Io)? {
+            Some(status) => check(status),
+            None => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("terminating editor");
+                #[cfg(feature = "log")]
+                log::info!("Terminating editor {}", child.id());
+                terminate(&mut child).map_err(Error::Io)?;
+                match cancel {
+                    Some(cancel) if cancel.load(Ordering::Relaxed) => Err(Error::Cancelled),
+                    _ => Err(Error::TimedOut),
+                }
+            }
+        }
+    }
+
+    /// Saves the terminal's settings if they should be restored. This must be called
+    /// before the editor is opened.
+    pub(super) fn save_terminal(mut self) -> Self {
+        if self.restore_terminal {
+            self.terminal = TerminalState::save();
+        }
+        self
+    }
+
+    /// Checks the editor's exit status, failing if it's non-zero and that's an error.
+    pub(super) fn check(&self, status: ExitStatus) -> Result<ExitStatus, Error> {
+        #[cfg(feature = "tracing")]
+        tracing::info!(%status, "editor exited");
+        #[cfg(feature = "log")]
+        log::info!("Editor exited with {status}");
+        if let Some(AfterExit(hook)) = &self.after_exit {
+            let mut hook = hook.lock().unwrap_or_else(PoisonError::into_inner);
+            hook(&status);
+        }
+        if self.fail_on_nonzero && !status.success() {
+            return Err(Error::EditorExited {
+                status,
+                stderr: None,
+            });
+        }
+        Ok(status)
+    }
+}
+
+/// Reads an editor's stderr in the background, keeping the end of it.
+struct StderrCapture {
+    /// The end of the editor's stderr.
+    buffer: Arc<Mutex<Vec<u8>>>,
+    /// Receives a message when stderr is closed.
+    closed: mpsc::Receiver<()>,
+}
+
+impl StderrCapture {
+    /// The most bytes of stderr that are kept.
+    const MAX_LEN: usize = 8 * 1024;
+    /// How long to wait for stderr to close after the editor exits.
+    const CLOSE_TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// Starts reading `stderr` in a new thread.
+    fn spawn(mut stderr: ChildStderr) -> Self {
+        use std::io::Read;
+
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let (sender, closed) = mpsc::channel();
+        thread::spawn({
+            let buffer = Arc::clone(&buffer);
+            move || {
+                let mut chunk = [0; 4096];
+                while let Ok(read @ 1..) = stderr.read(&mut chunk) {
+                    let mut buffer = buffer.lock().unwrap_or_else(PoisonError::into_inner);
+                    buffer.extend_from_slice(&chunk[..read]);
+                    let excess = buffer.len().saturating_sub(Self::MAX_LEN);
+                    buffer.drain(..excess);
+                }
+                let _ = sender.send(());
+            }
+        });
+        Self { buffer, closed }
+    }
+
+    /// Gets the end of the editor's stderr.
+    fn finish(self) -> String {
+        // NOTE Processes started by the editor, like a graphical editor's server, can
+        //      keep stderr open after the editor exits.
+        let _ = self.closed.recv_timeout(Self::CLOSE_TIMEOUT);
+        let buffer = self.buffer.lock().unwrap_or_else(PoisonError::into_inner);
+        String::from_utf8_lossy(&buffer).trim_end().to_string()
+    }
+}
+
+/// Polls the editor until it exits, the `deadline` passes, or `cancel` is set. Returns
+/// `None` if the editor didn't exit.
+fn wait_until(
+    child: &mut Child,
+    deadline: Option<Instant>,
+    cancel: Option<&AtomicBool>,
+) -> io::Result<Option<ExitStatus>> {
+    let mut interval = Duration::from_millis(1);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Ok(None);
+        }
+        let sleep = match deadline {
+            Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                Some(remaining) => interval.min(remaining),
+                None => return Ok(None),
+            },
+            None => interval,
+        };
+        thread::sleep(sleep);
+        interval = (interval * 2).min(Waiter::MAX_POLL_INTERVAL);
+    }
+}
+
+/// Ignores `SIGINT` and `SIGQUIT` until dropped, when the previous handlers are
+/// restored.
+#[cfg(unix)]
+struct IgnoredSignals {
+    /// The signals and their previous handlers.
+    previous: Vec<(libc::c_int, libc::sigaction)>,
+}
+
+#[cfg(unix)]
+impl IgnoredSignals {
+    /// The signals to ignore.
+    const SIGNALS: [libc::c_int; 2] = [libc::SIGINT, libc::SIGQUIT];
+
+    /// Starts ignoring the signals.
+    fn new() -> io::Result<Self> {
+        use std::mem::MaybeUninit;
+
+        let mut ignored = Self {
+            previous: Vec::with_capacity(Self::SIGNALS.len()),
+        };
+        for signal in Self::SIGNALS {
+            // SAFETY: sigaction is plain data, so zeroes are valid, and the mask is
+            //         initialized by sigemptyset.
+            let mut ignore: libc::sigaction = unsafe { std::mem::zeroed() };
+            ignore.sa_sigaction = libc::SIG_IGN;
+            unsafe { libc::sigemptyset(&mut ignore.sa_mask) };
+            let mut previous = MaybeUninit::uninit();
+            // SAFETY: Both pointers are valid, and the previous handler is initialized
+            //         when this succeeds.
+            if unsafe { libc::sigaction(signal, &ignore, previous.as_mut_ptr()) } != 0 {
+                // NOTE Dropping restores the signals that were already ignored.
+                return Err(io::Error::last_os_error());
+            }
+            ignored
+                .previous
+                .push((signal, unsafe { previous.assume_init() }));
+        }
+        Ok(ignored)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for IgnoredSignals {
+    fn drop(&mut self) {
+        for (signal, previous) in self.previous.iter().rev() {
+            // SAFETY: The previous handler was returned by sigaction.
+            unsafe { libc::sigaction(*signal, previous, std::ptr::null_mut()) };
+        }
+    }
+}
+
+/// Asks the editor to exit with `SIGTERM`, and kills it if it doesn't exit in time.
+#[cfg(unix)]
+fn terminate(child: &mut Child) -> io::Result<()> {
+    // NOTE Process IDs fit in a pid_t, Child::id just returns them as a u32.
+    let pid = child.id() as libc::pid_t;
+    // SAFETY: The child hasn't been waited for, so its ID hasn't been reused.
+    if unsafe { libc::kill(pid, libc::SIGTERM) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let deadline = Instant::now() + Waiter::KILL_GRACE_PERIOD;
+    if wait_until(child, Some(deadline), None)?.is_none() {
+        child.kill()?;
+        child.wait()?;
+    }
+    Ok(())
+}
+
+/// Kills the editor. On Windows, this calls `TerminateProcess`.
+#[cfg(not(unix))]
+fn terminate(child: &mut Child) -> io::Result<()> {
+    child.kill()?;
+    child.wait()?;
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    /// Gets the current handler of `signal`.
+    fn handler(signal: libc::c_int) -> libc::sighandler_t {
+        let mut current = std::mem::MaybeUninit::<libc::sigaction>::uninit();
+        let result = unsafe { libc::sigaction(signal, std::ptr::null(), current.as_mut_ptr()) };
+        assert_eq!(0, result);
+        unsafe { current.assume_init() }.sa_sigaction
+    }
+
+    /// Set when the test binary is run again to test signals in its own process.
+    const SUBPROCESS_VAR: &str = "FIND_EDITOR_SIGNALS_SUBPROCESS";
+
+    #[test]
+    fn test_ignore_signals() {
+        // NOTE Signal handlers are shared by the whole process, so the test runs in its
+        //      own process to avoid interrupting the other tests.
+        if std::env::var_os(SUBPROCESS_VAR).is_none() {
+            let output = Command::new(std::env::current_exe().unwrap())
+                .args(["--exact", "finder::wait::tests::test_ignore_signals"])
+                .env(SUBPROCESS_VAR, "1")
+                .output()
+                .unwrap();
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert!(output.status.success(), "{stdout}");
+            assert!(stdout.contains("1 passed"), "{stdout}");
+            return;
+        }
+        let before = handler(libc::SIGINT);
+        // NOTE The parent is interrupted after it starts waiting.
+        let child = Command::new("sh")
+            .args(["-c", "sleep 0.2; kill -INT $PPID"])
+            .spawn()
+            .unwrap();
+        let waiter = Waiter {
+            ignore_signals: true,
+            ..Waiter::default()
+        };
+        assert!(waiter.wait(child).unwrap().success());
+        assert_eq!(before, handler(libc::SIGINT));
+    }
+}
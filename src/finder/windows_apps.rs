@@ -0,0 +1,199 @@
+//! Utilities for finding Microsoft Store App Execution Aliases on Windows.
+//!
+//! These are zero-byte reparse points in `%LOCALAPPDATA%\Microsoft\WindowsApps`, like
+//! `python.exe` and `wt.exe`. They can be run, but can't be followed, so searching
+//! `%PATH%` can miss them.
+use std::ffi::{OsStr, OsString, c_void};
+use std::fs;
+use std::os::windows::fs::{MetadataExt, OpenOptionsExt};
+use std::os::windows::io::{AsRawHandle, RawHandle};
+use std::path::{Path, PathBuf};
+
+/// The attribute of a file that is a reparse point.
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+/// The flag to open a reparse point itself instead of following it.
+const FILE_FLAG_OPEN_REPARSE_POINT: u32 = 0x0020_0000;
+/// The reparse tag of App Execution Aliases.
+const IO_REPARSE_TAG_APPEXECLINK: u32 = 0x8000_001B;
+/// The `FILE_INFO_BY_HANDLE_CLASS` to get a file's attributes and reparse tag.
+const FILE_ATTRIBUTE_TAG_INFO: i32 = 9;
+/// The extensions of executables when `%PATHEXT%` isn't set.
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// Finds `command` among the App Execution Aliases in `local_app_data`. If `command`
+/// has no extension, each extension in `pathext` is tried, in order.
+pub(super) fn find(
+    command: &OsStr,
+    local_app_data: Option<OsString>,
+    pathext: Option<OsString>,
+) -> Option<PathBuf> {
+    let command = Path::new(command);
+    if command.components().count() != 1 || command.has_root() {
+        return None;
+    }
+    let dir = Path::new(&local_app_data?).join(r"Microsoft\WindowsApps");
+    if command.extension().is_some() {
+        let path = dir.join(command);
+        return is_alias(&path).then_some(path);
+    }
+    let pathext = pathext.unwrap_or_else(|| OsString::from(DEFAULT_PATHEXT));
+    pathext
+        .to_string_lossy()
+        .split(';')
+        .filter(|extension| !extension.is_empty())
+        .map(|extension| {
+            let mut name = command.as_os_str().to_owned();
+            name.push(extension);
+            dir.join(name)
+        })
+        .find(|path| is_alias(path))
+}
+
+/// `FILE_ATTRIBUTE_TAG_INFO`, which is a file's attributes and reparse tag.
+#[repr(C)]
+#[derive(Default)]
+struct FileAttributeTagInfo {
+    file_attributes: u32,
+    reparse_tag: u32,
+}
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn GetFileInformationByHandleEx(
+        file: RawHandle,
+        class: i32,
+        info: *mut c_void,
+        size: u32,
+    ) -> i32;
+}
+
+/// Checks if `path` is an App Execution Alias, which is a file with the
+/// `IO_REPARSE_TAG_APPEXECLINK` reparse tag.
+pub(super) fn is_alias(path: &Path) -> bool {
+    let is_reparse_point = fs::symlink_metadata(path).is_ok_and(|metadata| {
+        !metadata.is_dir() && metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0
+    });
+    // NOTE Other reparse points, like dangling symlinks, can't be followed either, but
+    //      they can't be run.
+    is_reparse_point && reparse_tag(path) == Some(IO_REPARSE_TAG_APPEXECLINK)
+}
+
+/// Gets the reparse tag of `path`, without following it.
+fn reparse_tag(path: &Path) -> Option<u32> {
+    let file = fs::OpenOptions::new()
+        .access_mode(0)
+        .custom_flags(FILE_FLAG_OPEN_REPARSE_POINT)
+        .open(path)
+        .ok()?;
+    let mut info = FileAttributeTagInfo::default();
+    // SAFETY: The handle is open, and `info` is the size that is given.
+    let result = unsafe {
+        GetFileInformationByHandleEx(
+            file.as_raw_handle(),
+            FILE_ATTRIBUTE_TAG_INFO,
+            (&raw mut info).cast(),
+            size_of::<FileAttributeTagInfo>() as u32,
+        )
+    };
+    (result != 0).then_some(info.reparse_tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io, ptr};
+
+    /// The control code to set a file's reparse point.
+    const FSCTL_SET_REPARSE_POINT: u32 = 0x0009_00A4;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn DeviceIoControl(
+            device: RawHandle,
+            code: u32,
+            input: *const c_void,
+            input_size: u32,
+            output: *mut c_void,
+            output_size: u32,
+            returned: *mut u32,
+            overlapped: *mut c_void,
+        ) -> i32;
+    }
+
+    /// Creates an App Execution Alias at `path`.
+    fn create_alias(path: &Path) {
+        let file = fs::File::create(path).unwrap();
+        // NOTE The data is a version, followed by the package, the app, and the target
+        //      executable as nul-terminated strings.
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend(
+            [
+                "Editor.Package",
+                "Editor.Package!App",
+                r"C:\Editor\editor.exe",
+            ]
+            .iter()
+            .flat_map(|string| string.encode_utf16().chain([0]))
+            .flat_map(u16::to_le_bytes),
+        );
+        let mut buffer = IO_REPARSE_TAG_APPEXECLINK.to_le_bytes().to_vec();
+        buffer.extend((data.len() as u16).to_le_bytes());
+        buffer.extend([0, 0]);
+        buffer.extend(data);
+        let mut returned = 0;
+        // SAFETY: The handle is open, and the buffer is the size that is given.
+        let result = unsafe {
+            DeviceIoControl(
+                file.as_raw_handle(),
+                FSCTL_SET_REPARSE_POINT,
+                buffer.as_ptr().cast(),
+                buffer.len() as u32,
+                ptr::null_mut(),
+                0,
+                &mut returned,
+                ptr::null_mut(),
+            )
+        };
+        assert_ne!(0, result, "{}", io::Error::last_os_error());
+    }
+
+    #[test]
+    fn test_find_alias() {
+        let local_app_data = tempfile::tempdir().unwrap();
+        let dir = local_app_data.path().join(r"Microsoft\WindowsApps");
+        fs::create_dir_all(&dir).unwrap();
+        let alias = dir.join("editor.exe");
+        create_alias(&alias);
+        assert!(is_alias(&alias));
+        let actual = find(
+            OsStr::new("editor"),
+            Some(local_app_data.path().into()),
+            None,
+        );
+        assert_eq!(Some(alias), actual);
+    }
+
+    #[test]
+    fn test_find_regular_file() {
+        let local_app_data = tempfile::tempdir().unwrap();
+        let dir = local_app_data.path().join(r"Microsoft\WindowsApps");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("editor.exe"), "").unwrap();
+        let actual = find(
+            OsStr::new("editor"),
+            Some(local_app_data.path().into()),
+            None,
+        );
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn test_find_path() {
+        let actual = find(
+            OsStr::new(r"C:\bin\editor"),
+            Some(OsString::from(r"C:\Users\me\AppData\Local")),
+            None,
+        );
+        assert_eq!(None, actual);
+    }
+}
@@ -0,0 +1,96 @@
+//! Utilities for opening Windows editors from the Windows Subsystem for Linux (WSL).
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+/// Translates the absolute path `file` into a Windows path with `wslpath -w`, if this
+/// is running in WSL and `editor` is a Windows program.
+///
+/// Returns `None` if the path doesn't need to be translated, or can't be. Relative
+/// paths aren't translated, because Windows programs run from WSL already start in the
+/// translated working directory.
+pub(super) fn windows_path(editor: &Path, file: &Path) -> Option<PathBuf> {
+    if !file.is_absolute() || !is_windows_program(editor) || !is_wsl() {
+        return None;
+    }
+    let output = Command::new("wslpath")
+        .arg("-w")
+        .arg(file)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = output.stdout.trim_ascii();
+    (!path.is_empty()).then(|| PathBuf::from(OsStr::from_bytes(path)))
+}
+
+/// Checks if this is running in WSL, using `/proc/version`.
+fn is_wsl() -> bool {
+    static IS_WSL: OnceLock<bool> = OnceLock::new();
+    *IS_WSL.get_or_init(|| {
+        std::fs::read_to_string("/proc/version").is_ok_and(|version| is_wsl_version(&version))
+    })
+}
+
+/// Checks if the contents of `/proc/version` are from a WSL kernel, like
+/// `Linux version 5.15.153.1-microsoft-standard-WSL2`.
+fn is_wsl_version(version: &str) -> bool {
+    let version = version.to_ascii_lowercase();
+    version.contains("microsoft") || version.contains("wsl")
+}
+
+/// Checks if `editor` is a Windows program, because it's an `.exe` or it's on a
+/// Windows drive, like `/mnt/c`.
+fn is_windows_program(editor: &Path) -> bool {
+    let is_exe = editor
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("exe"));
+    let mut components = editor.components();
+    let is_on_drive = components.next() == Some(Component::RootDir)
+        && components.next() == Some(Component::Normal(OsStr::new("mnt")))
+        && components.next().is_some_and(|component| {
+            matches!(component.as_os_str().as_bytes(), [letter] if letter.is_ascii_alphabetic())
+        });
+    is_exe || is_on_drive
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::wsl2(
+        "Linux version 5.15.153.1-microsoft-standard-WSL2 (root@1c602f52c2e4)",
+        true
+    )]
+    #[case::wsl1("Linux version 4.4.0-19041-Microsoft (Microsoft@Microsoft.com)", true)]
+    #[case::linux("Linux version 6.8.0-45-generic (buildd@lcy02-amd64-115)", false)]
+    fn test_is_wsl_version(#[case] version: &str, #[case] expected: bool) {
+        assert_eq!(expected, is_wsl_version(version));
+    }
+
+    #[rstest]
+    #[case::exe("notepad.exe", true)]
+    #[case::upper_exe("/usr/local/bin/NOTEPAD.EXE", true)]
+    #[case::drive("/mnt/c/Program Files/Microsoft VS Code/bin/code", true)]
+    #[case::linux("/usr/bin/vim", false)]
+    #[case::mount("/mnt/data/bin/vim", false)]
+    #[case::relative("mnt/c/vim", false)]
+    fn test_is_windows_program(#[case] editor: &str, #[case] expected: bool) {
+        assert_eq!(expected, is_windows_program(Path::new(editor)));
+    }
+
+    #[test]
+    fn test_windows_path_linux_editor() {
+        assert_eq!(
+            None,
+            windows_path(Path::new("/usr/bin/vim"), Path::new("/home/user/notes.txt"))
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Module for the formats that values can be edited in.
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::error::Error as ErrorTrait;
+
+/// A boxed error from serializing or deserializing a value.
+pub(crate) type BoxError = Box<dyn ErrorTrait + Send + Sync>;
+
+/// A format that a value can be serialized to and edited in.
+///
+/// Each format is enabled by the feature with the same name, like `toml`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// JSON, pretty-printed.
+    #[cfg(feature = "json")]
+    Json,
+    /// TOML.
+    #[cfg(feature = "toml")]
+    Toml,
+    /// YAML.
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    /// The file extension for this format, without the leading `.`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "toml")]
+    /// # {
+    /// use find_editor::Format;
+    ///
+    /// assert_eq!("toml", Format::Toml.extension());
+    /// # }
+    /// ```
+    pub fn extension(self) -> &'static str {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json => "json",
+            #[cfg(feature = "toml")]
+            Self::Toml => "toml",
+            #[cfg(feature = "yaml")]
+            Self::Yaml => "yaml",
+        }
+    }
+
+    /// Serializes `value` to text in this format.
+    #[cfg_attr(
+        not(any(feature = "json", feature = "toml", feature = "yaml")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn serialize<T>(self, value: &T) -> Result<String, BoxError>
+    where
+        T: Serialize + ?Sized,
+    {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json => Ok(serde_json::to_string_pretty(value)? + "\n"),
+            #[cfg(feature = "toml")]
+            Self::Toml => Ok(toml::to_string_pretty(value)?),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => Ok(serde_yaml::to_string(value)?),
+        }
+    }
+
+    /// Deserializes a value from text in this format.
+    #[cfg_attr(
+        not(any(feature = "json", feature = "toml", feature = "yaml")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn deserialize<T>(self, text: &str) -> Result<T, BoxError>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json => Ok(serde_json::from_str(text)?),
+            #[cfg(feature = "toml")]
+            Self::Toml => Ok(toml::from_str(text)?),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => Ok(serde_yaml::from_str(text)?),
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "json", feature = "toml", feature = "yaml")))]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        name: String,
+        retries: u32,
+    }
+
+    #[rstest]
+    #[cfg_attr(feature = "json", case::json(Format::Json))]
+    #[cfg_attr(feature = "toml", case::toml(Format::Toml))]
+    #[cfg_attr(feature = "yaml", case::yaml(Format::Yaml))]
+    fn test_round_trip(#[case] format: Format) {
+        let config = Config {
+            name: String::from("find-editor"),
+            retries: 3,
+        };
+        let text = format.serialize(&config).unwrap();
+        let actual: Config = format.deserialize(&text).unwrap();
+        assert_eq!(config, actual);
+    }
+}
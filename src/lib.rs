@@ -1,40 +1,148 @@
 //! Find and open an editor.
 //!
-//! If no editor is found, this library will always fall back to `notepad.exe` on
-//! Windows and `vi` on every other platform.
+//! If no editor is found, this library will fall back to `notepad.exe` on Windows and
+//! `vi` on every other platform. With the `which` feature on Windows, Notepad++ and VS
+//! Code are preferred to Notepad if they're installed. See
+//! [`Finder::DEFAULT_FALLBACKS`]. Use [`Finder::with_fallback`] to pick a different
+//! fallback.
 //!
-//! Use [`Finder`] for more advanced usage.
+//! Use [`Finder`] for more advanced usage, and [`EditorInfo`] to learn about
+//! well-known editors.
+//!
+//! This crate also compiles for WebAssembly, like `wasm32-wasip1` and
+//! `wasm32-unknown-unknown`. Editors are found from environment variables where an
+//! environment exists, but opening an editor fails with [`Error::UnsupportedPlatform`]
+//! because processes can't be spawned. The `async-process` and `tokio` features don't
+//! support WebAssembly.
 //!
 //! # Features
 //!
-//! - `open` - Provides [`open_editor`].
-//! - `split` - Provides [`split_editor_name`], which can help with multi-word editors
-//!   like `code --wait`.
-//! - `which` - Provides [`which_editor`], which finds the editor on `$PATH`. Calling an
-//!   executable on Windows can find and run an executable in the current directory.
-//!   [`which_editor`] helps *prevent* calling an executable in the current directory.
-#[cfg(any(feature = "open", feature = "split", feature = "which"))]
-pub use error::Error;
-pub use finder::Finder;
+//! - `async-process` - Like `tokio`, but works with any async runtime, like smol or
+//!   async-std. This is used instead of tokio when both features are enabled.
+//! - `capi` - Provides the [`capi`] module, a C API for applications that aren't
+//!   written in Rust.
+//! - `cli` - Builds the `find-editor` command, which prints the editor, or opens a
+//!   file with `find-editor open FILE`.
+//! - `git` - Provides [`FinderBuilder::git`], which looks up git's editor from
+//!   `$GIT_EDITOR` and `git config core.editor`.
+//! - `json`, `toml`, `yaml` - Enable the [`Format`] with the same name for
+//!   [`edit_serde`]. Each of these enables `serde`. With `which`, `json` also provides
+//!   [`Finder::resolve_json`], which describes the editor as JSON.
+//! - `log` - Like `tracing`, but emits [log](https://docs.rs/log) records instead.
+//! - `miette` - Implements [`miette::Diagnostic`](https://docs.rs/miette) for
+//!   [`Error`], with codes, help, and labels for the editor.
+//! - `open` - Provides [`open_editor`], [`open_editor_at`], [`open_editor_many`],
+//!   [`open_editor_status`], [`open_editor_timeout`], [`open_scratch`],
+//!   [`spawn_editor`], [`command_for`], [`OpenOptions`], and [`EditorSession`]. With
+//!   `which`, the editor's command is found on `$PATH` before it's run. Without it,
+//!   the platform finds the command, which can run an executable in the current
+//!   directory on Windows.
+//! - `prompt` - Provides [`Finder::select_interactively`], which lets the user pick an
+//!   editor from a numbered menu, like Debian's `select-editor`.
+//! - `serde` - Provides [`edit_serde`], which edits a value serialized in a [`Format`].
+//!   Enable a format's feature to use it. Also implements `Serialize` and `Deserialize`
+//!   for [`Finder`]'s configuration, [`ResolvedEditor`], and related types.
+//! - `split` - Provides [`split_editor_name`] and [`split_editor_name_os`], which help
+//!   with multi-word editors like `code --wait`, and [`SplitStyle`] to choose how
+//!   they're split.
+//! - `tempedit` - Provides [`edit_string`], [`edit_bytes`], and [`EditBuilder`], which
+//!   edit content in a temporary file.
+//! - `tokio` - Provides [`open_editor_async`], [`OpenOptions::open_async`], and, with
+//!   `tempedit`, [`edit_string_async`], which wait for the editor without blocking a
+//!   tokio runtime.
+//! - `tracing` - Emits [tracing](https://docs.rs/tracing) spans and events when an
+//!   editor is resolved, split, found on `$PATH`, spawned, and waited for.
+//! - `which` - Provides [`which_editor`] and [`which_editor_os`], which find the editor
+//!   on `$PATH`, and [`Finder::probe_installed`], which finds installed editors.
+//!   Calling an executable on Windows can find and run an executable in the current
+//!   directory. [`which_editor`] helps *prevent* calling an executable in the current
+//!   directory.
+//! - `windows-registry` - Provides [`FinderBuilder::windows_registry`], which looks up
+//!   the default text editor in the Windows registry.
+pub use editor_info::{EditorInfo, GotoSyntax};
+#[cfg(feature = "serde")]
+pub use error::EditError;
+pub use error::{Error, ErrorKind};
+#[cfg(feature = "tempedit")]
+pub use finder::EditBuilder;
+#[cfg(feature = "which")]
+pub use finder::InstalledEditor;
+#[cfg(feature = "split")]
+pub use finder::SplitStyle;
+#[cfg(feature = "open")]
+pub use finder::{
+    EditedFile, EditorSession, MacApp, OpenFallback, OpenOptions, ProcessGroup, TerminalMode,
+};
+pub use finder::{
+    EditorSource, EnvSnapshot, EnvSource, Explanation, Finder, FinderBuilder, Outcome, ProcessEnv,
+    ResolvedEditor, Resolver, Source, Step,
+};
+#[cfg(feature = "serde")]
+pub use format::Format;
 #[cfg(feature = "split")]
 pub use shell_words::ParseError;
 use std::ffi::OsString;
 #[cfg(feature = "open")]
 use std::path::Path;
-#[cfg(feature = "which")]
+#[cfg(any(feature = "open", feature = "which"))]
 use std::path::PathBuf;
+#[cfg(feature = "open")]
+use std::process::{Child, Command, ExitStatus};
+use std::sync::OnceLock;
+#[cfg(feature = "open")]
+use std::time::Duration;
 #[cfg(feature = "which")]
 pub use which::Error as WhichError;
 
-#[cfg(any(feature = "open", feature = "split", feature = "which"))]
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "miette")]
+mod diagnostic;
+mod editor_info;
 mod error;
 mod finder;
+#[cfg(feature = "serde")]
+mod format;
+
+/// A [`Result`](std::result::Result) with this crate's [`Error`] by default.
+///
+/// # Example
+///
+/// ```rust
+/// fn editor() -> find_editor::Result<String> {
+///     find_editor::Finder::new().try_editor_name()
+/// }
+/// ```
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Gets the default [`Finder`], which is shared by the whole process.
+///
+/// This is created the first time it's used. With the `which` feature, it's
+/// [cached](Finder::cached), so the editor's command is only found once per process.
+/// Call [`Finder::invalidate`] to find it again. The other free functions, like
+/// [`editor_name`], use this finder.
+///
+/// # Example
+///
+/// ```rust
+/// let resolved = find_editor::global().resolve();
+/// println!("Using {:?} from {}", resolved.name(), resolved.source());
+/// ```
+pub fn global() -> &'static Finder {
+    static GLOBAL: OnceLock<Finder> = OnceLock::new();
+    GLOBAL.get_or_init(|| {
+        let finder = Finder::new();
+        #[cfg(feature = "which")]
+        let finder = finder.cached();
+        finder
+    })
+}
 
 /// Gets the name of an editor as a [`String`].
 ///
 /// See [`Finder::editor_name`] for more information.
 pub fn editor_name() -> String {
-    Finder::new().editor_name()
+    global().editor_name()
 }
 
 /// Gets the name of an editor as an [`OsString`].
@@ -42,7 +150,33 @@ pub fn editor_name() -> String {
 /// See [`Finder::editor_name_os`] for more information.
 #[inline]
 pub fn editor_name_os() -> OsString {
-    Finder::new().editor_name_os()
+    global().editor_name_os()
+}
+
+/// Gets the name of an editor as a [`String`] without falling back to a default editor.
+///
+/// See [`Finder::try_editor_name`] for more information.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use find_editor::try_editor_name;
+///
+/// match try_editor_name() {
+///     Ok(editor) => println!("Your editor is {editor}"),
+///     Err(_) => eprintln!("Please set $EDITOR"),
+/// }
+/// ```
+pub fn try_editor_name() -> Result<String, Error> {
+    global().try_editor_name()
+}
+
+/// Gets the name of an editor as an [`OsString`] without falling back to a default
+/// editor.
+///
+/// See [`Finder::try_editor_name_os`] for more information.
+pub fn try_editor_name_os() -> Result<OsString, Error> {
+    global().try_editor_name_os()
 }
 
 /// Splits the editor into its command and any arguments.
@@ -50,7 +184,16 @@ pub fn editor_name_os() -> OsString {
 /// See [`Finder::split_editor_name`] for more information.
 #[cfg(feature = "split")]
 pub fn split_editor_name() -> Result<(String, Vec<String>), Error> {
-    Finder::new().split_editor_name()
+    global().split_editor_name()
+}
+
+/// Splits the editor into its command and any arguments, without losing bytes that
+/// aren't valid UTF-8.
+///
+/// See [`Finder::split_editor_name_os`] for more information.
+#[cfg(feature = "split")]
+pub fn split_editor_name_os() -> Result<(OsString, Vec<OsString>), Error> {
+    global().split_editor_name_os()
 }
 
 /// Gets the editor's command path and any arguments that should be passed to it.
@@ -66,7 +209,16 @@ pub fn split_editor_name() -> Result<(String, Vec<String>), Error> {
 /// ```
 #[cfg(feature = "which")]
 pub fn which_editor() -> Result<(PathBuf, Vec<String>), Error> {
-    Finder::new().which_editor()
+    global().which_editor()
+}
+
+/// Gets the editor's command path and any arguments that should be passed to it,
+/// without losing bytes that aren't valid UTF-8.
+///
+/// See [`Finder::which_editor_os`] for more information.
+#[cfg(feature = "which")]
+pub fn which_editor_os() -> Result<(PathBuf, Vec<OsString>), Error> {
+    global().which_editor_os()
 }
 
 /// Opens an editor to edit `file`. Set `wait` to `true` to make this function wait
@@ -88,5 +240,143 @@ pub fn open_editor<P>(file: P, wait: bool) -> Result<(), Error>
 where
     P: AsRef<Path>,
 {
-    Finder::new().open_editor(file, wait)
+    global().open_editor(file, wait)
+}
+
+/// Opens an editor to edit `file` without blocking the async runtime.
+///
+/// See [`Finder::open_editor_async`] for more information.
+#[cfg(any(feature = "async-process", feature = "tokio"))]
+pub async fn open_editor_async<P>(file: P, wait: bool) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    global().open_editor_async(file, wait).await
+}
+
+/// Opens an editor to edit all of `files` in a single invocation. Set `wait` to `true`
+/// to make this function wait until the editor is closed before returning.
+///
+/// See [`Finder::open_editor_many`] for more information.
+#[cfg(feature = "open")]
+pub fn open_editor_many<P, I>(files: I, wait: bool) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = P>,
+{
+    global().open_editor_many(files, wait)
+}
+
+/// Opens an editor without a file, like a scratch buffer.
+///
+/// See [`Finder::open_scratch`] for more information.
+#[cfg(feature = "open")]
+pub fn open_scratch(wait: bool) -> Result<Option<PathBuf>, Error> {
+    global().open_scratch(wait)
+}
+
+/// Opens an editor to edit `file`, waits until the editor is closed, and returns the
+/// editor's exit status.
+///
+/// See [`Finder::open_editor_status`] for more information.
+#[cfg(feature = "open")]
+pub fn open_editor_status<P>(file: P) -> Result<ExitStatus, Error>
+where
+    P: AsRef<Path>,
+{
+    global().open_editor_status(file)
+}
+
+/// Opens an editor to edit `file`, and waits up to `timeout` for the editor to be
+/// closed before terminating it.
+///
+/// See [`Finder::open_editor_timeout`] for more information.
+#[cfg(feature = "open")]
+pub fn open_editor_timeout<P>(file: P, timeout: Duration) -> Result<ExitStatus, Error>
+where
+    P: AsRef<Path>,
+{
+    global().open_editor_timeout(file, timeout)
+}
+
+/// Opens an editor to edit `file` at a `line` and, optionally, a `column`, and waits
+/// until the editor is closed.
+///
+/// See [`Finder::open_editor_at`] for more information.
+#[cfg(feature = "open")]
+pub fn open_editor_at<P>(file: P, line: usize, column: Option<usize>) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    global().open_editor_at(file, line, column)
+}
+
+/// Opens an editor to edit `file` without waiting, and returns the editor's process.
+///
+/// See [`Finder::spawn_editor`] for more information.
+#[cfg(feature = "open")]
+pub fn spawn_editor<P>(file: P) -> Result<Child, Error>
+where
+    P: AsRef<Path>,
+{
+    global().spawn_editor(file)
+}
+
+/// Builds the [`Command`] that would open an editor to edit `file`, without running it.
+///
+/// See [`Finder::command_for`] for more information.
+#[cfg(feature = "open")]
+pub fn command_for<P>(file: P) -> Result<Command, Error>
+where
+    P: AsRef<Path>,
+{
+    global().command_for(file)
+}
+
+/// Edits `initial` in an editor, and returns the edited text.
+///
+/// See [`Finder::edit_string`] for more information.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use find_editor::edit_string;
+///
+/// let message = edit_string("# Write your message here\n").expect("Should edit the message");
+/// ```
+#[cfg(feature = "tempedit")]
+pub fn edit_string(initial: &str) -> Result<String, Error> {
+    global().edit_string(initial)
+}
+
+/// Edits `initial` in an editor without blocking the async runtime, and returns the
+/// edited text.
+///
+/// See [`Finder::edit_string_async`] for more information.
+#[cfg(all(
+    feature = "tempedit",
+    any(feature = "async-process", feature = "tokio")
+))]
+pub async fn edit_string_async(initial: &str) -> Result<String, Error> {
+    global().edit_string_async(initial).await
+}
+
+/// Edits `initial` in an editor, and returns the edited bytes.
+///
+/// See [`Finder::edit_bytes`] for more information.
+#[cfg(feature = "tempedit")]
+pub fn edit_bytes(initial: &[u8]) -> Result<Vec<u8>, Error> {
+    global().edit_bytes(initial)
+}
+
+/// Edits `value` in an editor as text in the given `format`, and returns the edited
+/// value.
+///
+/// See [`Finder::edit_serde`] for more information.
+#[cfg(feature = "serde")]
+pub fn edit_serde<T>(value: &T, format: Format) -> Result<T, EditError>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    global().edit_serde(value, format)
 }